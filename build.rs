@@ -0,0 +1,231 @@
+//! Generates the Unicode lookup tables used by the `idna` module.
+//!
+//! The tables are read from the Unicode 15.1.0 data files in `data/`, in the format the Unicode
+//! Consortium publishes them, and written to `$OUT_DIR/unicode_tables.rs`, which `src/unicode_tables.rs` includes.
+//! All files have to be replaced together when moving to a newer Unicode version.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DATA_FILES: [&str; 4] = [
+    "IdnaMappingTable.txt",
+    "UnicodeData.txt",
+    "DerivedNormalizationProps.txt",
+    "DerivedJoiningType.txt",
+];
+
+fn main() {
+    let data = Path::new(env!("CARGO_MANIFEST_DIR")).join("data");
+    for file in DATA_FILES {
+        println!("cargo:rerun-if-changed=data/{}", file);
+    }
+
+    let mut output = String::from("// Generated by build.rs from the files in data/, do not edit.\n\n");
+    let characters = unicode_data(&data);
+    write_idna_mapping(&mut output, &data);
+    write_bidi_classes(&mut output, &characters);
+    write_marks(&mut output, &characters);
+    write_combining_classes(&mut output, &characters);
+    write_normalization(&mut output, &data, &characters);
+    write_joining_types(&mut output, &data);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(out_dir.join("unicode_tables.rs"), output).expect("failed to write unicode_tables.rs");
+}
+
+/// Returns the `;`-separated fields of every data line, without comments.
+fn records(data: &Path, file: &str) -> Vec<Vec<String>> {
+    let path = data.join(file);
+    let text = fs::read_to_string(&path).unwrap_or_else(|error| panic!("failed to read {}: {}", path.display(), error));
+
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| line.split(';').map(|field| field.trim().to_string()).collect())
+        .collect()
+}
+
+/// Parses `XXXX` or `XXXX..YYYY`.
+fn code_point_range(field: &str) -> (u32, u32) {
+    let parse = |hex: &str| u32::from_str_radix(hex, 16).unwrap_or_else(|_| panic!("invalid code point {:?}", hex));
+    match field.split_once("..") {
+        Some((start, end)) => (parse(start), parse(end)),
+        None => (parse(field), parse(field)),
+    }
+}
+
+fn code_points(field: &str) -> Vec<u32> {
+    field.split_whitespace().map(|hex| code_point_range(hex).0).collect()
+}
+
+/// The fields of `UnicodeData.txt` the tables are built from.
+struct Character {
+    first: u32,
+    last: u32,
+    general_category: String,
+    combining_class: u8,
+    bidi_class: String,
+    decomposition: String,
+}
+
+/// Reads `UnicodeData.txt`, joining the `<..., First>` and `<..., Last>` lines of large ranges.
+fn unicode_data(data: &Path) -> Vec<Character> {
+    let mut characters: Vec<Character> = Vec::new();
+    for record in records(data, "UnicodeData.txt") {
+        let (code_point, _) = code_point_range(&record[0]);
+        if record[1].ends_with(", Last>") {
+            characters.last_mut().expect("range without a first line").last = code_point;
+            continue;
+        }
+
+        characters.push(Character {
+            first: code_point,
+            last: code_point,
+            general_category: record[2].clone(),
+            combining_class: record[3].parse().expect("invalid combining class"),
+            bidi_class: record[4].clone(),
+            decomposition: record[5].clone(),
+        });
+    }
+
+    characters
+}
+
+/// Joins adjacent code points with the same value into `(first, last, value)` ranges.
+fn ranges<T: PartialEq>(values: impl IntoIterator<Item = (u32, u32, T)>) -> Vec<(u32, u32, T)> {
+    let mut ranges: Vec<(u32, u32, T)> = Vec::new();
+    for (first, last, value) in values {
+        match ranges.last_mut() {
+            Some(range) if range.1 + 1 == first && range.2 == value => range.1 = last,
+            _ => ranges.push((first, last, value)),
+        }
+    }
+
+    ranges
+}
+
+fn write_idna_mapping(output: &mut String, data: &Path) {
+    let mut pool: Vec<u32> = Vec::new();
+    let mut rows = String::new();
+
+    for record in records(data, "IdnaMappingTable.txt") {
+        let (start, end) = code_point_range(&record[0]);
+        let status = match record[1].as_str() {
+            "valid" => "Valid",
+            "ignored" => "Ignored",
+            "mapped" => "Mapped",
+            "deviation" => "Deviation",
+            "disallowed" => "Disallowed",
+            "disallowed_STD3_valid" => "DisallowedStd3Valid",
+            "disallowed_STD3_mapped" => "DisallowedStd3Mapped",
+            status => panic!("unknown IDNA status {:?}", status),
+        };
+
+        let mapping = match record.get(2) {
+            Some(field) if status != "Valid" => code_points(field),
+            _ => Vec::new(),
+        };
+        let offset = pool.len();
+        pool.extend(&mapping);
+
+        writeln!(rows, "    ({:#X}, {:#X}, Status::{}, {}, {}),", start, end, status, offset, mapping.len()).unwrap();
+    }
+
+    output.push_str("/// `(first, last, status, mapping offset, mapping length)`, sorted by code point.\n");
+    output.push_str("pub(crate) const IDNA_MAPPING: &[(u32, u32, Status, u16, u8)] = &[\n");
+    output.push_str(&rows);
+    output.push_str("];\n\n");
+
+    output.push_str("/// The code points the entries of `IDNA_MAPPING` map to.\n");
+    output.push_str("pub(crate) const IDNA_MAPPING_POOL: &[char] = &[");
+    for code_point in pool {
+        write!(output, "'\\u{{{:X}}}', ", code_point).unwrap();
+    }
+    output.push_str("];\n\n");
+}
+
+fn write_bidi_classes(output: &mut String, characters: &[Character]) {
+    output.push_str("/// `(first, last, class)`, sorted by code point.\n");
+    output.push_str("pub(crate) const BIDI_CLASSES: &[(u32, u32, BidiClass)] = &[\n");
+    for (first, last, class) in ranges(characters.iter().map(|c| (c.first, c.last, &c.bidi_class))) {
+        writeln!(output, "    ({:#X}, {:#X}, BidiClass::{}),", first, last, class).unwrap();
+    }
+    output.push_str("];\n\n");
+}
+
+fn write_marks(output: &mut String, characters: &[Character]) {
+    output.push_str("/// `(first, last)` of the code points with General_Category Mark, sorted by code point.\n");
+    output.push_str("pub(crate) const MARKS: &[(u32, u32)] = &[\n");
+    let marks = characters.iter().filter(|c| c.general_category.starts_with('M'));
+    for (first, last, _) in ranges(marks.map(|c| (c.first, c.last, ()))) {
+        writeln!(output, "    ({:#X}, {:#X}),", first, last).unwrap();
+    }
+    output.push_str("];\n\n");
+}
+
+fn write_combining_classes(output: &mut String, characters: &[Character]) {
+    output.push_str("/// `(first, last, class)` of the non-zero Canonical_Combining_Class values, sorted by code point.\n");
+    output.push_str("pub(crate) const COMBINING_CLASSES: &[(u32, u32, u8)] = &[\n");
+    let classes = characters.iter().filter(|c| c.combining_class != 0);
+    for (first, last, class) in ranges(classes.map(|c| (c.first, c.last, c.combining_class))) {
+        writeln!(output, "    ({:#X}, {:#X}, {}),", first, last, class).unwrap();
+    }
+    output.push_str("];\n\n");
+}
+
+fn write_normalization(output: &mut String, data: &Path, characters: &[Character]) {
+    let exclusions: Vec<(u32, u32)> = records(data, "DerivedNormalizationProps.txt")
+        .into_iter()
+        .filter(|record| record[1] == "Full_Composition_Exclusion")
+        .map(|record| code_point_range(&record[0]))
+        .collect();
+    let is_excluded = |c: u32| exclusions.iter().any(|&(start, end)| (start..=end).contains(&c));
+
+    let mut decompositions: Vec<(u32, u32, u32)> = Vec::new();
+    let mut compositions: Vec<(u32, u32, u32)> = Vec::new();
+    // Compatibility decompositions start with a `<tag>`, Hangul syllables decompose algorithmically
+    let canonical = characters.iter().filter(|c| !c.decomposition.is_empty() && !c.decomposition.starts_with('<'));
+    for character in canonical {
+        let c = character.first;
+        match code_points(&character.decomposition)[..] {
+            [single] => decompositions.push((c, single, 0)),
+            [first, second] => {
+                decompositions.push((c, first, second));
+                if !is_excluded(c) {
+                    compositions.push((first, second, c));
+                }
+            }
+            _ => panic!("canonical decomposition of {:04X} is not one or two code points", c),
+        }
+    }
+    decompositions.sort_unstable();
+    compositions.sort_unstable();
+
+    output.push_str("/// `(code point, first, second)` canonical decompositions, `second` is 0 for singletons.\n");
+    output.push_str("pub(crate) const DECOMPOSITIONS: &[(u32, u32, u32)] = &[\n");
+    for (c, first, second) in decompositions {
+        writeln!(output, "    ({:#X}, {:#X}, {:#X}),", c, first, second).unwrap();
+    }
+    output.push_str("];\n\n");
+
+    output.push_str("/// `(first, second, composite)` primary composites, sorted by pair.\n");
+    output.push_str("pub(crate) const COMPOSITIONS: &[(u32, u32, u32)] = &[\n");
+    for (first, second, c) in compositions {
+        writeln!(output, "    ({:#X}, {:#X}, {:#X}),", first, second, c).unwrap();
+    }
+    output.push_str("];\n\n");
+}
+
+fn write_joining_types(output: &mut String, data: &Path) {
+    output.push_str("/// `(first, last, type)` of the code points that are not Non_Joining, sorted by code point.\n");
+    output.push_str("pub(crate) const JOINING_TYPES: &[(u32, u32, JoiningType)] = &[\n");
+    for record in records(data, "DerivedJoiningType.txt") {
+        let (start, end) = code_point_range(&record[0]);
+        if record[1] != "U" {
+            writeln!(output, "    ({:#X}, {:#X}, JoiningType::{}),", start, end, record[1]).unwrap();
+        }
+    }
+    output.push_str("];\n");
+}
@@ -0,0 +1,125 @@
+#!/usr/bin/env python3
+"""Reconstructs the two data files of `data/` that are not copies of the official files.
+
+`IdnaMappingTable.txt` and `UnicodeData.txt` are the unmodified Unicode 15.1.0 files.
+`DerivedNormalizationProps.txt` and `DerivedJoiningType.txt` are written by this script in the
+format of the official files, with only the lines `build.rs` reads:
+
+- Full_Composition_Exclusion: every canonical decomposition of `UnicodeData.txt` that NFC
+  does not recompose, using Python's `unicodedata`, which has to be Unicode 15.1.0.
+- Joining_Type: the values of Perl's `Unicode::UCD`, code points that were assigned later
+  than its Unicode version get T for the General_Category Mn, Me and Cf like the UCD derives them.
+
+Replace both files with the official ones from https://www.unicode.org/Public/15.1.0/ucd/
+(`DerivedNormalizationProps.txt` and `extracted/DerivedJoiningType.txt`) and delete this script.
+
+Usage: python3 data/reconstruct.py
+"""
+
+import bisect
+import os
+import subprocess
+import sys
+import unicodedata
+
+VERSION = "15.1.0"
+DATA = os.path.dirname(os.path.abspath(__file__))
+TRANSPARENT_CATEGORIES = {"Mn", "Me", "Cf"}
+SHORT_JOINING_TYPES = {"Dual_Joining": "D", "Join_Causing": "C", "Left_Joining": "L", "Right_Joining": "R",
+                       "Transparent": "T", "D": "D", "C": "C", "L": "L", "R": "R", "T": "T"}
+
+
+def unicode_data():
+    """Yields `(code point, fields)` for every line of `UnicodeData.txt`."""
+    with open(os.path.join(DATA, "UnicodeData.txt"), encoding="utf-8") as file:
+        for line in file:
+            fields = line.rstrip("\n").split(";")
+            yield int(fields[0], 16), fields
+
+
+def ranges(values):
+    """Yields `(start, end, value)` for runs of equal values of a sorted `(code point, value)` list."""
+    start = previous = value = None
+    for code_point, current in values:
+        if start is not None and (code_point != previous + 1 or current != value):
+            yield start, previous, value
+            start = None
+        if start is None:
+            start, value = code_point, current
+        previous = code_point
+    if start is not None:
+        yield start, previous, value
+
+
+def code_points(start, end):
+    return "%04X" % start if start == end else "%04X..%04X" % (start, end)
+
+
+def write(name, description, lines):
+    with open(os.path.join(DATA, name), "w", encoding="utf-8") as file:
+        file.write("# %s\n# Version: %s\n#\n# %s\n# Reconstructed by data/reconstruct.py, not the official file.\n\n"
+                   % (name, VERSION, description))
+        file.write("\n".join(lines) + "\n")
+
+
+def composition_exclusions():
+    for code_point, fields in unicode_data():
+        decomposition = fields[5]
+        if decomposition and not decomposition.startswith("<") and unicodedata.normalize("NFC", chr(code_point)) != chr(code_point):
+            yield code_point, "Full_Composition_Exclusion"
+
+
+def perl_joining_types():
+    """Returns the Joining_Type ranges and the Unicode version of Perl's `Unicode::UCD`."""
+    script = ('use Unicode::UCD qw(prop_invmap); my ($list, $map) = prop_invmap("Joining_Type");'
+              'print Unicode::UCD::UnicodeVersion(), "\\n"; print "$list->[$_] $map->[$_]\\n" for 0 .. $#$list;')
+    output = run_perl(script).splitlines()
+    return output[0], [(int(start), value) for start, value in (line.split() for line in output[1:])]
+
+
+def perl_unassigned():
+    """Returns the inversion list of the code points that are unassigned in Perl's `Unicode::UCD`."""
+    return [int(start) for start in run_perl('use Unicode::UCD qw(prop_invlist); print "$_\\n" for prop_invlist("gc=Cn");').split()]
+
+
+def run_perl(script):
+    return subprocess.run(["perl", "-e", script], check=True, capture_output=True, text=True).stdout
+
+
+def joining_types():
+    perl_version, perl_ranges = perl_joining_types()
+    unassigned = perl_unassigned()
+
+    def perl_value(code_point):
+        return perl_ranges[bisect.bisect_right(perl_ranges, (code_point, "\uffff")) - 1][1]
+
+    def perl_assigned(code_point):
+        return bisect.bisect_right(unassigned, code_point) % 2 == 0
+
+    values = []
+    for code_point, fields in unicode_data():
+        if fields[1].endswith(", Last>"):
+            continue
+        if perl_assigned(code_point):
+            value = SHORT_JOINING_TYPES.get(perl_value(code_point))
+        else:
+            value = "T" if fields[2] in TRANSPARENT_CATEGORIES else None
+        if value:
+            values.append((code_point, value))
+    return perl_version, values
+
+
+def main():
+    if unicodedata.unidata_version != VERSION:
+        sys.exit("unicodedata is Unicode %s, not %s" % (unicodedata.unidata_version, VERSION))
+
+    write("DerivedNormalizationProps.txt", "Full_Composition_Exclusion from UnicodeData.txt and Python's unicodedata.",
+          ["%-14s; %s" % (code_points(start, end), value) for start, end, value in ranges(composition_exclusions())])
+
+    perl_version, values = joining_types()
+    write("DerivedJoiningType.txt", "Joining_Type from Perl's Unicode %s data, later code points derived from UnicodeData.txt."
+          % perl_version, ["%-14s; %s" % (code_points(start, end), value) for start, end, value in ranges(values)])
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -0,0 +1,307 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::de::value::{CowStrDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{self, Deserialize, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::form;
+use crate::SerdeError;
+
+/// Deserializes a value from `application/x-www-form-urlencoded` data.
+///
+/// The input is parsed with [`form::parse`](crate::form::parse).
+/// Structs and maps see every name once, all values of a repeated name are collected
+/// and can be deserialized into a sequence. A repeated name deserialized into a single value is an error.
+/// Sequences of `(name, value)` pairs see every pair in order.
+///
+/// # Arguments
+///
+/// * `input` - The form data, without a leading `?`.
+///
+/// # Returns
+///
+/// The deserialized value, or a [`SerdeError`] if the data does not fit the type.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Search {
+///     query: String,
+///     page: u32,
+///     #[serde(default)]
+///     tags: Vec<String>,
+///     lang: Option<String>,
+/// }
+///
+/// let search: Search = url_encor::from_str("query=rust+lang&page=2&tags=a&tags=b%26c").unwrap();
+/// assert_eq!(search, Search {
+///     query: "rust lang".into(),
+///     page: 2,
+///     tags: vec!["a".into(), "b&c".into()],
+///     lang: None,
+/// });
+/// ```
+pub fn from_str<'de, T: Deserialize<'de>>(input: &'de str) -> Result<T, SerdeError> {
+    T::deserialize(FormDeserializer { input })
+}
+
+/// Deserializes the whole form data.
+struct FormDeserializer<'de> {
+    input: &'de str,
+}
+
+impl<'de> FormDeserializer<'de> {
+    /// Collects all values per name, keeping the order in which the names appear first.
+    fn grouped(&self) -> Vec<(Part<'de>, Values<'de>)> {
+        let mut grouped: Vec<(Part<'de>, Values<'de>)> = Vec::new();
+        let mut positions: HashMap<Cow<'de, str>, usize> = HashMap::new();
+        for (name, value) in form::parse(self.input) {
+            match positions.get(&name) {
+                Some(&position) => grouped[position].1.values.push(value),
+                None => {
+                    positions.insert(name.clone(), grouped.len());
+                    grouped.push((Part(name.clone()), Values { name, values: vec![value] }));
+                }
+            }
+        }
+
+        grouped
+    }
+}
+
+impl<'de> de::Deserializer<'de> for FormDeserializer<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let mut map = MapDeserializer::new(self.grouped().into_iter());
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let pairs = form::parse(self.input).map(|(name, value)| (Part(name), Part(value)));
+        let mut seq = MapDeserializer::new(pairs);
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit_struct tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+/// A single decoded name or value.
+struct Part<'de>(Cow<'de, str>);
+
+impl<'de> IntoDeserializer<'de, SerdeError> for Part<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+            match self.0.parse() {
+                Ok(value) => visitor.$visit(value),
+                Err(error) => Err(SerdeError::new(format!("invalid value {:?}: {}", self.0, error))),
+            }
+        })*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Part<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.0 {
+            Cow::Borrowed(value) => visitor.visit_borrowed_str(value),
+            Cow::Owned(value) => visitor.visit_string(value),
+        }
+    }
+
+    deserialize_parsed!(
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    );
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if self.0.is_empty() {
+            visitor.visit_unit()
+        } else {
+            Err(SerdeError::new(format!("invalid value {:?}: expected an empty value", self.0)))
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        let variant: CowStrDeserializer<'de, SerdeError> = self.0.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// All values of one name.
+struct Values<'de> {
+    name: Cow<'de, str>,
+    values: Vec<Cow<'de, str>>,
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for Values<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Values<'de> {
+    /// Returns the only value, a repeated name can't be deserialized into a single value.
+    fn single(mut self) -> Result<Part<'de>, SerdeError> {
+        match self.values.len() {
+            1 => Ok(Part(self.values.remove(0))),
+            count => Err(SerdeError::new(format!("name {:?} appears {} times, expected a single value", self.name, count))),
+        }
+    }
+}
+
+macro_rules! deserialize_single {
+    ($($method:ident),* $(,)?) => {
+        $(fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+            de::Deserializer::$method(self.single()?, visitor)
+        })*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Values<'de> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if self.values.len() == 1 {
+            self.single()?.deserialize_any(visitor)
+        } else {
+            self.deserialize_seq(visitor)
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let mut seq = SeqDeserializer::new(self.values.into_iter().map(Part));
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        self.single()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.single()?.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    deserialize_single!(
+        deserialize_bool, deserialize_i8, deserialize_i16, deserialize_i32, deserialize_i64, deserialize_i128,
+        deserialize_u8, deserialize_u16, deserialize_u32, deserialize_u64, deserialize_u128,
+        deserialize_f32, deserialize_f64, deserialize_char, deserialize_str, deserialize_string,
+        deserialize_bytes, deserialize_byte_buf, deserialize_unit, deserialize_map, deserialize_identifier,
+    );
+}
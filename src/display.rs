@@ -0,0 +1,228 @@
+use std::fmt::{Display, Formatter, Write};
+use std::iter::FusedIterator;
+
+use crate::{from_hex, EncodeSet, HexCase, Mode};
+
+/// Every possible byte value, so single decoded bytes can be handed out as `'static` slices.
+static ALL_BYTES: [u8; 256] = all_bytes();
+
+const fn all_bytes() -> [u8; 256] {
+    let mut array = [0u8; 256];
+    let mut index = 0;
+    while index < 256 {
+        array[index] = index as u8;
+        index += 1;
+    }
+
+    array
+}
+
+/// # Lazily encoded text
+///
+/// Implements [`Display`] by writing the escaped text chunk by chunk straight into the formatter,
+/// so no intermediate `String` is allocated.
+///
+/// Created by [`display`] and [`display_with`].
+#[derive(Clone, Copy, Debug)]
+pub struct PercentEncoded<'a> {
+    str_to_encode: &'a str,
+    set: EncodeSet,
+    mode: Mode,
+    case: HexCase,
+}
+
+impl PercentEncoded<'_> {
+    /// Sets the [`Mode`] that decides how spaces get encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use url_encor::Mode;
+    ///
+    /// assert_eq!(url_encor::display("a b").mode(Mode::FormUrlEncoded).to_string(), "a+b");
+    /// ```
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the [`HexCase`] of the hex digits in escapes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use url_encor::HexCase;
+    ///
+    /// assert_eq!(url_encor::display("a/b").hex_case(HexCase::Lower).to_string(), "a%2fb");
+    /// ```
+    pub fn hex_case(mut self, case: HexCase) -> Self {
+        self.case = case;
+        self
+    }
+}
+
+impl Display for PercentEncoded<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bytes = self.str_to_encode.as_bytes();
+        let hex_digits = self.case.digits();
+        let mut run_start = 0;
+
+        for (index, &current_byte) in bytes.iter().enumerate() {
+            let space_as_plus = current_byte == b' ' && self.mode.space_as_plus();
+            if space_as_plus || self.set.contains(current_byte) {
+                // Write everything that did not need encoding in one go
+                if run_start < index {
+                    f.write_str(&self.str_to_encode[run_start..index])?;
+                }
+                run_start = index + 1;
+
+                if space_as_plus {
+                    f.write_char('+')?;
+                } else {
+                    f.write_char('%')?;
+                    f.write_char(hex_digits[(current_byte >> 4) as usize] as char)?;
+                    f.write_char(hex_digits[(current_byte & 0xF) as usize] as char)?;
+                }
+            }
+        }
+
+        f.write_str(&self.str_to_encode[run_start..])
+    }
+}
+
+/// Lazily encodes a string using url_encor.
+///
+/// The returned value writes the same text as [`encode`](crate::encode) when it gets formatted.
+///
+/// # Arguments
+///
+/// * `str_to_encode` - A string slice that holds the text to be URL encoded.
+///
+/// # Returns
+///
+/// A [`PercentEncoded`] that encodes the text while being displayed.
+///
+/// # Examples
+///
+/// ```
+/// let query = "rust lang";
+/// let url = format!("https://example.com/search?q={}", url_encor::display(query));
+/// assert_eq!(url, "https://example.com/search?q=rust%20lang");
+/// ```
+pub fn display(str_to_encode: &str) -> PercentEncoded<'_> {
+    display_with(str_to_encode, &EncodeSet::DEFAULT)
+}
+
+/// Lazily encodes a string using a custom [`EncodeSet`].
+///
+/// The returned value writes the same text as [`encode_with`](crate::encode_with) when it gets formatted.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{display_with, EncodeSet};
+///
+/// let path = format!("https://example.com{}", display_with("/my files/", &EncodeSet::PATH));
+/// assert_eq!(path, "https://example.com/my%20files/");
+/// ```
+pub fn display_with<'a>(str_to_encode: &'a str, set: &EncodeSet) -> PercentEncoded<'a> {
+    PercentEncoded {
+        str_to_encode,
+        set: *set,
+        mode: Mode::Rfc3986,
+        case: HexCase::Upper,
+    }
+}
+
+/// # Lazily decoded bytes
+///
+/// An iterator over chunks of decoded bytes.
+/// Text without escapes is handed out as a slice of the input,
+/// every decoded escape is handed out as a single byte.
+///
+/// Created by [`decode_chunks`] and [`decode_chunks_with_mode`].
+#[derive(Clone, Debug)]
+pub struct PercentDecoded<'a> {
+    bytes: &'a [u8],
+    mode: Mode,
+}
+
+impl<'a> Iterator for PercentDecoded<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        let (&first, _) = bytes.split_first()?;
+
+        let chunk_length = if first == b'%' && bytes.len() > 2 {
+            if let (Some(n1), Some(n2)) = (from_hex(bytes[1]), from_hex(bytes[2])) {
+                // Decode a valid percent-encoded sequence
+                let decoded = ((n1 << 4) | n2) as usize;
+                self.bytes = &bytes[3..];
+                return Some(&ALL_BYTES[decoded..decoded + 1]);
+            }
+            // Invalid percent-encoding, hand out the original characters
+            3
+        } else if first == b'+' && self.mode.space_as_plus() {
+            self.bytes = &bytes[1..];
+            return Some(b" ");
+        } else {
+            // Non-encoded characters, hand out everything up to the next special byte
+            1 + bytes[1..]
+                .iter()
+                .position(|&byte| byte == b'%' || (byte == b'+' && self.mode.space_as_plus()))
+                .unwrap_or(bytes.len() - 1)
+        };
+
+        let (chunk, rest) = bytes.split_at(chunk_length);
+        self.bytes = rest;
+        Some(chunk)
+    }
+}
+
+impl FusedIterator for PercentDecoded<'_> {}
+
+impl PercentDecoded<'_> {
+    /// Collects the decoded chunks into a `Vec<u8>`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.clone().flatten().copied().collect()
+    }
+}
+
+/// Lazily decodes a URL-encoded string.
+///
+/// The chunks concatenate to the same bytes as [`decode_to_bytes`](crate::decode_to_bytes) returns.
+///
+/// # Arguments
+///
+/// * `str_to_decode` - A string slice that holds the text to be URL decoded.
+///
+/// # Returns
+///
+/// A [`PercentDecoded`] iterator over the decoded chunks.
+///
+/// # Examples
+///
+/// ```
+/// let chunks: Vec<&[u8]> = url_encor::decode_chunks("Hello%2C+World").collect();
+/// assert_eq!(chunks, [&b"Hello"[..], b",", b" ", b"World"]);
+/// ```
+pub fn decode_chunks(str_to_decode: &str) -> PercentDecoded<'_> {
+    decode_chunks_with_mode(str_to_decode, Mode::FormUrlEncoded)
+}
+
+/// Lazily decodes a URL-encoded string using the given [`Mode`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{decode_chunks_with_mode, Mode};
+///
+/// assert_eq!(decode_chunks_with_mode("/c++/a%20b", Mode::Rfc3986).to_bytes(), b"/c++/a b");
+/// ```
+pub fn decode_chunks_with_mode(str_to_decode: &str, mode: Mode) -> PercentDecoded<'_> {
+    PercentDecoded {
+        bytes: str_to_decode.as_bytes(),
+        mode,
+    }
+}
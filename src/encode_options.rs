@@ -0,0 +1,59 @@
+use crate::{EncodeSet, HexCase, Mode};
+
+/// # How text gets encoded
+///
+/// Combines the [`EncodeSet`] of bytes to escape, the [`Mode`] that decides how spaces are written
+/// and the [`HexCase`] of the hex digits in escapes.
+/// The defaults encode like [`encode`](crate::encode).
+///
+/// ```
+/// use url_encor::{encode_with_options, EncodeOptions, EncodeSet, HexCase, Mode};
+///
+/// const FORM_LOWER: EncodeOptions = EncodeOptions::new()
+///     .set(EncodeSet::FORM_URLENCODED)
+///     .mode(Mode::FormUrlEncoded)
+///     .hex_case(HexCase::Lower);
+///
+/// assert_eq!(encode_with_options("a b/ä", &FORM_LOWER), "a+b%2f%c3%a4");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncodeOptions {
+    pub(crate) set: EncodeSet,
+    pub(crate) mode: Mode,
+    pub(crate) case: HexCase,
+}
+
+impl EncodeOptions {
+    /// Creates the default options: [`EncodeSet::DEFAULT`], [`Mode::Rfc3986`] and [`HexCase::Upper`].
+    pub const fn new() -> Self {
+        EncodeOptions {
+            set: EncodeSet::DEFAULT,
+            mode: Mode::Rfc3986,
+            case: HexCase::Upper,
+        }
+    }
+
+    /// Sets the [`EncodeSet`] of bytes that get escaped.
+    pub const fn set(mut self, set: EncodeSet) -> Self {
+        self.set = set;
+        self
+    }
+
+    /// Sets the [`Mode`] that decides how spaces get encoded.
+    pub const fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the [`HexCase`] of the hex digits in escapes.
+    pub const fn hex_case(mut self, case: HexCase) -> Self {
+        self.case = case;
+        self
+    }
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        EncodeOptions::new()
+    }
+}
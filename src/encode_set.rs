@@ -0,0 +1,195 @@
+use crate::preprocessing::PREPROCESSED_ARRAY;
+
+/// # A set of bytes that should get percent-encoded
+///
+/// This is a 256-entry bitmap. A set bit means the byte **gets encoded**,
+/// a cleared bit means the byte is written as-is.
+///
+/// Sets are built with `const` methods, so custom sets can live in constants:
+///
+/// ```
+/// use url_encor::{encode_with, EncodeSet};
+///
+/// // Keep '/' so whole paths can be encoded in one go
+/// const PATH_SAFE: EncodeSet = EncodeSet::DEFAULT.remove(b'/');
+///
+/// assert_eq!(encode_with("/my files/a b", &PATH_SAFE), "/my%20files/a%20b");
+/// ```
+///
+/// Bytes outside the ASCII range are **always** encoded.
+/// Leaving them untouched could split a multi-byte UTF-8 sequence and produce invalid output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncodeSet {
+    bits: [u64; 4],
+}
+
+impl EncodeSet {
+    /// Encodes nothing but the bytes outside the ASCII range.
+    pub const NON_ASCII: EncodeSet = EncodeSet {
+        bits: [0, 0, u64::MAX, u64::MAX],
+    };
+
+    /// Encodes every byte that is not an unreserved character as defined by
+    /// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-2.3).
+    ///
+    /// This is the set used by [`encode`](crate::encode).
+    pub const DEFAULT: EncodeSet = EncodeSet::from_table(&PREPROCESSED_ARRAY);
+
+    /// The [C0 control percent-encode set](https://url.spec.whatwg.org/#c0-control-percent-encode-set)
+    /// of the WHATWG URL Standard.
+    ///
+    /// Encodes the C0 controls and every byte greater than `~`.
+    pub const C0_CONTROL: EncodeSet = EncodeSet::NON_ASCII.add_range(0x00, 0x1F).add(0x7F);
+
+    /// The [fragment percent-encode set](https://url.spec.whatwg.org/#fragment-percent-encode-set).
+    ///
+    /// The C0 control set plus space, `"`, `<`, `>` and `` ` ``.
+    pub const FRAGMENT: EncodeSet = EncodeSet::C0_CONTROL
+        .add(b' ')
+        .add(b'"')
+        .add(b'<')
+        .add(b'>')
+        .add(b'`');
+
+    /// The [query percent-encode set](https://url.spec.whatwg.org/#query-percent-encode-set).
+    ///
+    /// The C0 control set plus space, `"`, `#`, `<` and `>`.
+    pub const QUERY: EncodeSet = EncodeSet::C0_CONTROL
+        .add(b' ')
+        .add(b'"')
+        .add(b'#')
+        .add(b'<')
+        .add(b'>');
+
+    /// The [special-query percent-encode set](https://url.spec.whatwg.org/#special-query-percent-encode-set),
+    /// used for the query of special schemes like `http`.
+    ///
+    /// The query set plus `'`.
+    pub const SPECIAL_QUERY: EncodeSet = EncodeSet::QUERY.add(b'\'');
+
+    /// The [path percent-encode set](https://url.spec.whatwg.org/#path-percent-encode-set).
+    ///
+    /// The query set plus `?`, `^`, `` ` ``, `{` and `}`.
+    pub const PATH: EncodeSet = EncodeSet::QUERY
+        .add(b'?')
+        .add(b'^')
+        .add(b'`')
+        .add(b'{')
+        .add(b'}');
+
+    /// The [userinfo percent-encode set](https://url.spec.whatwg.org/#userinfo-percent-encode-set).
+    ///
+    /// The path set plus `/`, `:`, `;`, `=`, `@`, `[` to `^` and `|`.
+    pub const USERINFO: EncodeSet = EncodeSet::PATH
+        .add(b'/')
+        .add(b':')
+        .add(b';')
+        .add(b'=')
+        .add(b'@')
+        .add_range(b'[', b'^')
+        .add(b'|');
+
+    /// The [component percent-encode set](https://url.spec.whatwg.org/#component-percent-encode-set),
+    /// used by `encodeURIComponent`-like operations.
+    ///
+    /// The userinfo set plus `$` to `&`, `+` and `,`.
+    pub const COMPONENT: EncodeSet = EncodeSet::USERINFO
+        .add_range(b'$', b'&')
+        .add(b'+')
+        .add(b',');
+
+    /// The [application/x-www-form-urlencoded percent-encode set](https://url.spec.whatwg.org/#application-x-www-form-urlencoded-percent-encode-set).
+    ///
+    /// The component set plus `!`, `'` to `)` and `~`.
+    pub const FORM_URLENCODED: EncodeSet = EncodeSet::COMPONENT
+        .add(b'!')
+        .add_range(b'\'', b')')
+        .add(b'~');
+
+    /// The complement of the [attr-char](https://www.rfc-editor.org/rfc/rfc8187#section-3.2.1) set of RFC 8187,
+    /// used for `filename*` and other extended header parameters.
+    ///
+    /// The default set without `!`, `#`, `$`, `&`, `+`, `^`, `` ` `` and `|`.
+    pub const ATTR_CHAR: EncodeSet = EncodeSet::DEFAULT
+        .remove(b'!')
+        .remove(b'#')
+        .remove(b'$')
+        .remove(b'&')
+        .remove(b'+')
+        .remove(b'^')
+        .remove(b'`')
+        .remove(b'|');
+
+    /// Builds a set from a preprocessed table, where `0` marks a byte that should get encoded.
+    const fn from_table(table: &[u8; 256]) -> EncodeSet {
+        let mut set = EncodeSet::NON_ASCII;
+        let mut index = 0;
+        while index < 128 {
+            if table[index] == 0 {
+                set = set.add(index as u8);
+            }
+            index += 1;
+        }
+
+        set
+    }
+
+    /// Returns a copy of the set that also encodes `byte`.
+    pub const fn add(self, byte: u8) -> EncodeSet {
+        let mut bits = self.bits;
+        bits[(byte >> 6) as usize] |= 1 << (byte & 63);
+        EncodeSet { bits }
+    }
+
+    /// Returns a copy of the set that also encodes every byte from `start` to `end` (inclusive).
+    pub const fn add_range(self, start: u8, end: u8) -> EncodeSet {
+        let mut set = self;
+        let mut byte = start;
+        while byte <= end {
+            set = set.add(byte);
+            if byte == u8::MAX {
+                break;
+            }
+            byte += 1;
+        }
+
+        set
+    }
+
+    /// Returns a copy of the set that leaves `byte` as-is.
+    ///
+    /// Removing a non-ASCII byte has no effect, these always get encoded.
+    pub const fn remove(self, byte: u8) -> EncodeSet {
+        if !byte.is_ascii() {
+            return self;
+        }
+
+        let mut bits = self.bits;
+        bits[(byte >> 6) as usize] &= !(1 << (byte & 63));
+        EncodeSet { bits }
+    }
+
+    /// Returns a set that encodes every byte encoded by either `self` or `other`.
+    pub const fn union(self, other: EncodeSet) -> EncodeSet {
+        EncodeSet {
+            bits: [
+                self.bits[0] | other.bits[0],
+                self.bits[1] | other.bits[1],
+                self.bits[2] | other.bits[2],
+                self.bits[3] | other.bits[3],
+            ],
+        }
+    }
+
+    /// Returns `true` if `byte` gets encoded by this set.
+    #[inline]
+    pub const fn contains(&self, byte: u8) -> bool {
+        self.bits[(byte >> 6) as usize] & (1 << (byte & 63)) != 0
+    }
+}
+
+impl Default for EncodeSet {
+    fn default() -> Self {
+        EncodeSet::DEFAULT
+    }
+}
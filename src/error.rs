@@ -0,0 +1,352 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// # Error returned by the strict decoding functions
+///
+/// Every variant carries the byte offset into the **input** where the problem was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// A `%` was followed by a byte that is not a hexadecimal digit.
+    ///
+    /// `offset` points at the offending byte, not at the `%`.
+    InvalidHexDigit { offset: usize, byte: u8 },
+
+    /// The input ended before a `%` was followed by two hexadecimal digits.
+    ///
+    /// `offset` points at the `%`.
+    TruncatedEscape { offset: usize },
+
+    /// The decoded bytes are not valid UTF-8.
+    ///
+    /// `offset` points at the start of the input sequence that produced the first invalid byte.
+    InvalidUtf8 { offset: usize },
+}
+
+impl DecodeError {
+    /// Returns the byte offset into the input where the error was found.
+    pub fn offset(&self) -> usize {
+        match *self {
+            DecodeError::InvalidHexDigit { offset, .. }
+            | DecodeError::TruncatedEscape { offset }
+            | DecodeError::InvalidUtf8 { offset } => offset,
+        }
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidHexDigit { offset, byte } => {
+                write!(f, "invalid hex digit {:?} in escape at byte {}", *byte as char, offset)
+            }
+            DecodeError::TruncatedEscape { offset } => {
+                write!(f, "truncated escape at byte {}", offset)
+            }
+            DecodeError::InvalidUtf8 { offset } => {
+                write!(f, "decoded bytes are not valid UTF-8 at byte {}", offset)
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// # Error returned by [`to_string`](crate::to_string) and [`from_str`](crate::from_str)
+///
+/// Describes why a value could not be mapped to or from `application/x-www-form-urlencoded` data.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerdeError {
+    message: String,
+}
+
+#[cfg(feature = "serde")]
+impl SerdeError {
+    pub(crate) fn new(message: impl Display) -> Self {
+        SerdeError {
+            message: message.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for SerdeError {}
+
+#[cfg(feature = "serde")]
+impl serde::ser::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerdeError::new(msg)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for SerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        SerdeError::new(msg)
+    }
+}
+
+/// # Error returned by the nested query parser
+///
+/// Every variant carries the decoded name of the offending parameter.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NestedError {
+    /// The name has more bracket segments than allowed, like `a[b][c][d]` with a depth limit of `2`.
+    DepthLimitExceeded { name: String },
+
+    /// An array index, explicit like `a[500]` or implicit like the 500th `a[]`, is larger than allowed.
+    ArrayIndexLimitExceeded { name: String },
+
+    /// The name uses a value in two incompatible ways, like `a=1&a[b]=2` or `a[]=1&a[b]=2`.
+    TypeConflict { name: String },
+}
+
+impl Display for NestedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NestedError::DepthLimitExceeded { name } => write!(f, "parameter {:?} is nested too deeply", name),
+            NestedError::ArrayIndexLimitExceeded { name } => write!(f, "parameter {:?} exceeds the array index limit", name),
+            NestedError::TypeConflict { name } => write!(f, "parameter {:?} conflicts with an earlier parameter", name),
+        }
+    }
+}
+
+impl Error for NestedError {}
+
+/// # Error returned by the URL parser
+///
+/// Each variant corresponds to a failure of the
+/// [WHATWG basic URL parser](https://url.spec.whatwg.org/#concept-basic-url-parser).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UrlError {
+    /// The input has no scheme and no base URL was given, or the base URL can't be a base.
+    RelativeUrlWithoutBase,
+
+    /// A URL with a special scheme or with credentials has an empty host.
+    EmptyHost,
+
+    /// The host contains a forbidden code point.
+    InvalidHost,
+
+    /// The domain could not be converted to ASCII.
+    InvalidDomain,
+
+    /// The host looks like an IPv4 address but is not a valid one.
+    InvalidIpv4Address,
+
+    /// The host is enclosed in brackets but is not a valid IPv6 address.
+    InvalidIpv6Address,
+
+    /// The port contains a non-digit or is larger than `65535`.
+    InvalidPort,
+}
+
+impl Display for UrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UrlError::RelativeUrlWithoutBase => "relative URL without a base",
+            UrlError::EmptyHost => "empty host",
+            UrlError::InvalidHost => "invalid host character",
+            UrlError::InvalidDomain => "invalid domain",
+            UrlError::InvalidIpv4Address => "invalid IPv4 address",
+            UrlError::InvalidIpv6Address => "invalid IPv6 address",
+            UrlError::InvalidPort => "invalid port number",
+        })
+    }
+}
+
+impl Error for UrlError {}
+
+/// # Error returned by [`Uri::parse_strict`](crate::Uri::parse_strict)
+///
+/// Every variant carries the byte offset into the input where the RFC 3986 grammar was violated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UriError {
+    /// A byte that is not allowed at this position of the URI.
+    InvalidCharacter { offset: usize, byte: u8 },
+
+    /// A `%` that is not followed by two hexadecimal digits.
+    ///
+    /// `offset` points at the `%`.
+    InvalidPercentEncoding { offset: usize },
+
+    /// The text before the first `:` is neither a valid scheme nor a valid first path segment.
+    ///
+    /// `offset` points at the first byte that can't be part of a scheme.
+    InvalidScheme { offset: usize },
+
+    /// The host is enclosed in brackets but is neither an IPv6 address nor an `IPvFuture` literal.
+    ///
+    /// `offset` points at the `[`.
+    InvalidIpLiteral { offset: usize },
+}
+
+impl UriError {
+    /// Returns the byte offset into the input where the error was found.
+    pub fn offset(&self) -> usize {
+        match *self {
+            UriError::InvalidCharacter { offset, .. }
+            | UriError::InvalidPercentEncoding { offset }
+            | UriError::InvalidScheme { offset }
+            | UriError::InvalidIpLiteral { offset } => offset,
+        }
+    }
+}
+
+impl Display for UriError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UriError::InvalidCharacter { offset, byte } => {
+                write!(f, "invalid character {:?} at byte {}", *byte as char, offset)
+            }
+            UriError::InvalidPercentEncoding { offset } => {
+                write!(f, "invalid percent-encoding at byte {}", offset)
+            }
+            UriError::InvalidScheme { offset } => {
+                write!(f, "invalid scheme at byte {}", offset)
+            }
+            UriError::InvalidIpLiteral { offset } => {
+                write!(f, "invalid IP literal at byte {}", offset)
+            }
+        }
+    }
+}
+
+impl Error for UriError {}
+
+/// # Error returned by [`ext_value::parse`](crate::ext_value::parse) and [`ext_value::decode_parameters`](crate::ext_value::decode_parameters)
+///
+/// The offsets are relative to the parameter value that contains the error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExtValueError {
+    /// The input does not have the form `charset'language'value`.
+    Malformed,
+
+    /// The charset is neither `UTF-8` nor `ISO-8859-1`.
+    UnsupportedCharset,
+
+    /// A byte of the value is neither an `attr-char` nor part of an escape.
+    ///
+    /// For a section of a continued parameter, `offset` is relative to the value of that section.
+    InvalidCharacter { offset: usize, byte: u8 },
+
+    /// A `%` that is not followed by two hexadecimal digits.
+    ///
+    /// `offset` points at the `%`, for a section of a continued parameter
+    /// it is relative to the value of that section.
+    InvalidPercentEncoding { offset: usize },
+
+    /// The decoded value is not valid UTF-8.
+    InvalidUtf8,
+
+    /// The numbers of a continued parameter have a gap, like `title*0` followed by `title*2`.
+    MissingContinuation,
+}
+
+impl Display for ExtValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtValueError::Malformed => f.write_str("expected charset'language'value"),
+            ExtValueError::UnsupportedCharset => f.write_str("unsupported charset"),
+            ExtValueError::InvalidCharacter { offset, byte } => {
+                write!(f, "invalid character {:?} at byte {}", *byte as char, offset)
+            }
+            ExtValueError::InvalidPercentEncoding { offset } => {
+                write!(f, "invalid percent-encoding at byte {}", offset)
+            }
+            ExtValueError::InvalidUtf8 => f.write_str("decoded value is not valid UTF-8"),
+            ExtValueError::MissingContinuation => f.write_str("missing continuation section"),
+        }
+    }
+}
+
+impl Error for ExtValueError {}
+
+/// # Error returned by [`punycode::encode`](crate::punycode::encode) and [`punycode::decode`](crate::punycode::decode)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PunycodeError {
+    /// The input contains a byte that is not allowed, like a non-ASCII character in encoded input.
+    ///
+    /// `offset` points at the offending byte.
+    InvalidCharacter { offset: usize },
+
+    /// The encoded input ended in the middle of a number.
+    UnexpectedEnd,
+
+    /// A computation exceeded the range of 32-bit integers.
+    Overflow,
+
+    /// The decoded value is not a Unicode scalar value.
+    InvalidCodePoint,
+
+    /// The input to decode is longer than [`MAX_DECODE_LENGTH`](crate::punycode::MAX_DECODE_LENGTH).
+    TooLong,
+}
+
+impl Display for PunycodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PunycodeError::InvalidCharacter { offset } => write!(f, "invalid character at byte {}", offset),
+            PunycodeError::UnexpectedEnd => f.write_str("unexpected end of input"),
+            PunycodeError::Overflow => f.write_str("punycode overflow"),
+            PunycodeError::InvalidCodePoint => f.write_str("decoded value is not a valid code point"),
+            PunycodeError::TooLong => f.write_str("input too long"),
+        }
+    }
+}
+
+impl Error for PunycodeError {}
+
+/// # Error returned by the [`idna`](crate::idna) functions
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdnaError {
+    /// The domain contains a code point that UTS #46 does not allow.
+    DisallowedCharacter { c: char },
+
+    /// An `xn--` label is not valid Punycode or decodes to a code point that is not valid,
+    /// or a label is too long to be encoded.
+    InvalidPunycode,
+
+    /// A decoded `xn--` label is not in Unicode Normalization Form C.
+    NotNormalized,
+
+    /// A label starts or ends with `-`, has `--` at its third and fourth position,
+    /// or looks like an `xn--` label after decoding.
+    InvalidHyphen,
+
+    /// A label starts with a combining mark.
+    LeadingCombiningMark,
+
+    /// A zero width joiner or non-joiner appears outside the contexts RFC 5892 allows.
+    InvalidJoiner,
+
+    /// A label of a domain with right-to-left characters breaks the rules of RFC 5893.
+    BidiRule,
+
+    /// The domain or one of its labels is empty or longer than DNS allows.
+    InvalidLength,
+}
+
+impl Display for IdnaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdnaError::DisallowedCharacter { c } => write!(f, "disallowed character {:?}", c),
+            IdnaError::InvalidPunycode => f.write_str("invalid punycode label"),
+            IdnaError::NotNormalized => f.write_str("label is not in normalization form C"),
+            IdnaError::InvalidHyphen => f.write_str("invalid hyphen in label"),
+            IdnaError::LeadingCombiningMark => f.write_str("label starts with a combining mark"),
+            IdnaError::InvalidJoiner => f.write_str("invalid zero width joiner or non-joiner"),
+            IdnaError::BidiRule => f.write_str("label breaks the bidi rule"),
+            IdnaError::InvalidLength => f.write_str("domain or label has an invalid length"),
+        }
+    }
+}
+
+impl Error for IdnaError {}
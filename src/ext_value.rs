@@ -0,0 +1,408 @@
+//! # Extended header parameter values
+//!
+//! Encoding and parsing of the `charset'language'value` syntax of
+//! [RFC 8187](https://www.rfc-editor.org/rfc/rfc8187), used for non-ASCII header parameters
+//! like the `filename*` of a `Content-Disposition` header.
+//!
+//! MIME headers can also split long values into numbered continuations like `title*0*=` and `title*1*=`,
+//! as defined by [RFC 2231](https://www.rfc-editor.org/rfc/rfc2231).
+//! See [`encode_continuations`] and [`decode_parameters`].
+//!
+//! # Examples
+//!
+//! ```
+//! use url_encor::ext_value;
+//!
+//! let header = format!("attachment; {}", ext_value::filename_parameters("€ rates.pdf"));
+//! assert_eq!(header, "attachment; filename=\"_ rates.pdf\"; filename*=UTF-8''%E2%82%AC%20rates.pdf");
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+
+use crate::{encode_with, from_hex, EncodeSet, ExtValueError};
+
+/// # A parsed extended value
+///
+/// Created by [`parse`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExtValue {
+    charset: String,
+    language: String,
+    value: String,
+}
+
+impl ExtValue {
+    /// Returns the charset as written, like `UTF-8`.
+    pub fn charset(&self) -> &str {
+        &self.charset
+    }
+
+    /// Returns the language tag, which is usually empty.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Returns the decoded value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Consumes the `ExtValue` and returns the decoded value.
+    pub fn into_value(self) -> String {
+        self.value
+    }
+}
+
+/// Encodes a value as `UTF-8''value`.
+///
+/// # Arguments
+///
+/// * `value` - The text to encode.
+///
+/// # Returns
+///
+/// A new `String` containing the extended value.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// assert_eq!(ext_value::encode("€ rates.pdf"), "UTF-8''%E2%82%AC%20rates.pdf");
+/// assert_eq!(ext_value::encode("a+b&c!.txt"), "UTF-8''a+b&c!.txt");
+/// ```
+pub fn encode(value: &str) -> String {
+    encode_with_language(value, "")
+}
+
+/// Encodes a value as `UTF-8'language'value`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// assert_eq!(ext_value::encode_with_language("£ rates", "en"), "UTF-8'en'%C2%A3%20rates");
+/// ```
+pub fn encode_with_language(value: &str, language: &str) -> String {
+    format!("UTF-8'{}'{}", language, encode_with(value, &EncodeSet::ATTR_CHAR))
+}
+
+/// Parses an extended value.
+///
+/// The charsets `UTF-8` and `ISO-8859-1` are supported, in any case, as required by RFC 8187.
+/// `US-ASCII`, which is common in mail, is accepted as well.
+///
+/// # Arguments
+///
+/// * `input` - The extended value, like `UTF-8''%E2%82%AC%20rates.pdf`.
+///
+/// # Returns
+///
+/// The parsed [`ExtValue`], or an [`ExtValueError`] if the input is malformed.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// let parsed = ext_value::parse("iso-8859-1'en'%A3%20rates").unwrap();
+/// assert_eq!(parsed.charset(), "iso-8859-1");
+/// assert_eq!(parsed.language(), "en");
+/// assert_eq!(parsed.value(), "£ rates");
+/// ```
+pub fn parse(input: &str) -> Result<ExtValue, ExtValueError> {
+    let (charset, language, value) = split_ext_value(input)?;
+    let value_offset = charset.len() + language.len() + 2;
+    let value = decode_charset(charset, decode_value(value, value_offset)?)?;
+
+    Ok(ExtValue {
+        charset: charset.to_string(),
+        language: language.to_string(),
+        value,
+    })
+}
+
+/// Splits `charset'language'value` into its parts.
+fn split_ext_value(input: &str) -> Result<(&str, &str, &str), ExtValueError> {
+    let mut parts = input.splitn(3, '\'');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(charset), Some(language), Some(value)) => Ok((charset, language, value)),
+        _ => Err(ExtValueError::Malformed),
+    }
+}
+
+/// Converts the decoded bytes to text, `US-ASCII` is accepted as a subset of `UTF-8`.
+fn decode_charset(charset: &str, bytes: Vec<u8>) -> Result<String, ExtValueError> {
+    if charset.eq_ignore_ascii_case("UTF-8") || charset.eq_ignore_ascii_case("US-ASCII") {
+        String::from_utf8(bytes).map_err(|_| ExtValueError::InvalidUtf8)
+    } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+        // Every ISO-8859-1 byte has the same value as its code point
+        Ok(bytes.into_iter().map(char::from).collect())
+    } else {
+        Err(ExtValueError::UnsupportedCharset)
+    }
+}
+
+/// Percent-decodes a value that may only contain `attr-char`s and escapes.
+fn decode_value(value: &str, value_offset: usize) -> Result<Vec<u8>, ExtValueError> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte == b'%' {
+            let escape = match (bytes.get(index + 1), bytes.get(index + 2)) {
+                (Some(&high), Some(&low)) => from_hex(high).zip(from_hex(low)),
+                _ => None,
+            };
+            let (high, low) = escape.ok_or(ExtValueError::InvalidPercentEncoding { offset: value_offset + index })?;
+            decoded.push(high << 4 | low);
+            index += 3;
+        } else if EncodeSet::ATTR_CHAR.contains(byte) {
+            return Err(ExtValueError::InvalidCharacter { offset: value_offset + index, byte });
+        } else {
+            decoded.push(byte);
+            index += 1;
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Returns the ASCII fallback for clients that don't understand extended values, as a quoted string.
+///
+/// Non-ASCII characters and controls are replaced by `_`, `"` and `\` are escaped.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// assert_eq!(ext_value::ascii_fallback("Über \"alles\".txt"), "\"_ber \\\"alles\\\".txt\"");
+/// ```
+pub fn ascii_fallback(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            ' '..='~' => quoted.push(c),
+            _ => quoted.push('_'),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
+
+/// Returns the `filename` parameter and, if the name is not plain ASCII, the `filename*` parameter.
+///
+/// Clients that understand `filename*` prefer it, older clients fall back to `filename`.
+///
+/// # Arguments
+///
+/// * `filename` - The name of the file.
+///
+/// # Returns
+///
+/// The parameters, joined with `; `, ready to be appended to `attachment; ` or `inline; `.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// assert_eq!(ext_value::filename_parameters("report.pdf"), "filename=\"report.pdf\"");
+/// assert_eq!(ext_value::filename_parameters("naïve.txt"), "filename=\"na_ve.txt\"; filename*=UTF-8''na%C3%AFve.txt");
+/// ```
+pub fn filename_parameters(filename: &str) -> String {
+    let fallback = ascii_fallback(filename);
+    if filename.bytes().all(|byte| (b' '..=b'~').contains(&byte)) {
+        return format!("filename={}", fallback);
+    }
+
+    format!("filename={}; filename*={}", fallback, encode(filename))
+}
+
+/// Encodes a parameter as `UTF-8` extended value, split into RFC 2231 continuations if it is too long.
+///
+/// If `name*=UTF-8''value` fits into `max_line_length` bytes, it is the only parameter.
+/// Otherwise the encoded value is split into `name*0*=UTF-8''…`, `name*1*=…` and so on,
+/// each at most `max_line_length` bytes long. Escapes are never split, so a line always
+/// holds at least one character, even if that exceeds the budget.
+///
+/// # Arguments
+///
+/// * `name` - The name of the parameter, like `title`.
+/// * `value` - The text to encode.
+/// * `max_line_length` - The maximum length of a single `name*N*=value` parameter.
+///
+/// # Returns
+///
+/// The parameters in order, to be joined with `;` and folded into the header.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// assert_eq!(ext_value::encode_continuations("title", "Größe", 78), ["title*=UTF-8''Gr%C3%B6%C3%9Fe"]);
+/// assert_eq!(ext_value::encode_continuations("title", "This is even more fun", 26), [
+///     "title*0*=UTF-8''This%20is",
+///     "title*1*=%20even%20more%20",
+///     "title*2*=fun",
+/// ]);
+/// ```
+pub fn encode_continuations(name: &str, value: &str, max_line_length: usize) -> Vec<String> {
+    let encoded = encode_with(value, &EncodeSet::ATTR_CHAR);
+    let single = format!("{}*=UTF-8''{}", name, encoded);
+    if single.len() <= max_line_length {
+        return vec![single];
+    }
+
+    let mut parameters = Vec::new();
+    let mut rest = encoded.as_str();
+    while parameters.is_empty() || !rest.is_empty() {
+        let section = parameters.len();
+        let mut parameter = format!("{}*{}*=", name, section);
+        if section == 0 {
+            parameter.push_str("UTF-8''");
+        }
+
+        let budget = max_line_length.saturating_sub(parameter.len());
+        let mut end = 0;
+        while end < rest.len() {
+            let length = if rest.as_bytes()[end] == b'%' { 3 } else { 1 };
+            if end > 0 && end + length > budget {
+                break;
+            }
+            end += length;
+        }
+
+        parameter.push_str(&rest[..end]);
+        parameters.push(parameter);
+        rest = &rest[end..];
+    }
+
+    parameters
+}
+
+/// A parameter while its continuations are collected.
+enum Parameter {
+    Plain(String),
+    Extended(String),
+    /// The sections by number, each with a flag telling if it is percent-encoded
+    Continued(BTreeMap<usize, (bool, String)>),
+}
+
+/// Decodes the parameters of a MIME header, reassembling RFC 2231 continuations.
+///
+/// The names are matched case-insensitively and returned without the `*` suffixes:
+///
+/// - `name*=charset'language'value` is decoded like [`parse`].
+/// - `name*0`, `name*1*`, … are sorted by number and joined. Sections ending in `*` are percent-decoded,
+///   the first one of them starts with `charset'language'`. The sections have to be numbered without gaps.
+/// - Other parameters are returned as they are.
+///
+/// If a name appears both plain and extended, the extended value wins, like RFC 6266 recommends.
+///
+/// # Arguments
+///
+/// * `parameters` - The `(name, value)` pairs of the header, with quoted strings already unquoted.
+///
+/// # Returns
+///
+/// The decoded `(name, value)` pairs in the order the names appeared first, or an [`ExtValueError`].
+/// The `offset` of an error in a continued parameter is relative to the value of its section,
+/// not to the joined value, so `%` at the start of `title*1*` is reported at offset 0.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::ext_value;
+///
+/// // The example from RFC 2231, section 4.1
+/// let parameters = ext_value::decode_parameters([
+///     ("title*0*", "us-ascii'en'This%20is%20even%20more%20"),
+///     ("title*1*", "%2A%2A%2Afun%2A%2A%2A%20"),
+///     ("title*2", "isn't it!"),
+/// ]).unwrap();
+/// assert_eq!(parameters, [("title".to_string(), "This is even more ***fun*** isn't it!".to_string())]);
+/// ```
+pub fn decode_parameters<I, K, V>(parameters: I) -> Result<Vec<(String, String)>, ExtValueError>
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut collected: Vec<(String, Parameter)> = Vec::new();
+    for parameter in parameters {
+        let (name, value) = parameter.borrow();
+        let (name, value) = (name.as_ref(), value.as_ref());
+
+        let (base_name, parameter) = match name.split_once('*') {
+            None => (name, Parameter::Plain(value.to_string())),
+            Some((base_name, "")) => (base_name, Parameter::Extended(parse(value)?.into_value())),
+            Some((base_name, section)) => {
+                let (number, is_encoded) = match section.strip_suffix('*') {
+                    Some(number) => (number, true),
+                    None => (section, false),
+                };
+                let number = number.parse().map_err(|_| ExtValueError::Malformed)?;
+                let sections = BTreeMap::from([(number, (is_encoded, value.to_string()))]);
+                (base_name, Parameter::Continued(sections))
+            }
+        };
+
+        match collected.iter_mut().find(|(existing, _)| existing.eq_ignore_ascii_case(base_name)) {
+            None => collected.push((base_name.to_string(), parameter)),
+            Some((_, existing)) => match (existing, parameter) {
+                (Parameter::Continued(sections), Parameter::Continued(new_sections)) => sections.extend(new_sections),
+                (_, Parameter::Plain(_)) => {}
+                (existing, parameter) => *existing = parameter,
+            },
+        }
+    }
+
+    collected
+        .into_iter()
+        .map(|(name, parameter)| {
+            let value = match parameter {
+                Parameter::Plain(value) | Parameter::Extended(value) => value,
+                Parameter::Continued(sections) => join_sections(sections)?,
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Joins the sections of a continued parameter and decodes the result.
+fn join_sections(sections: BTreeMap<usize, (bool, String)>) -> Result<String, ExtValueError> {
+    let mut charset = "UTF-8";
+    let mut bytes = Vec::new();
+
+    for (expected, (number, (is_encoded, value))) in sections.iter().enumerate() {
+        if *number != expected {
+            return Err(ExtValueError::MissingContinuation);
+        }
+
+        if !is_encoded {
+            bytes.extend_from_slice(value.as_bytes());
+        } else if *number == 0 {
+            let (first_charset, language, value) = split_ext_value(value)?;
+            charset = first_charset;
+            bytes.extend(decode_value(value, first_charset.len() + language.len() + 2)?);
+        } else {
+            bytes.extend(decode_value(value, 0)?);
+        }
+    }
+
+    decode_charset(charset, bytes)
+}
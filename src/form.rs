@@ -0,0 +1,253 @@
+//! # application/x-www-form-urlencoded
+//!
+//! Parsing and serializing of `application/x-www-form-urlencoded` data like `a=1&b=two+words&c`,
+//! as defined by the [WHATWG URL Standard](https://url.spec.whatwg.org/#application/x-www-form-urlencoded).
+
+use std::borrow::{Borrow, Cow};
+use std::fmt::{self, Write};
+use std::iter::FusedIterator;
+
+use crate::{decode_to_bytes_with_mode, display_with, EncodeSet, HexCase, Mode};
+
+/// # Iterator over the name-value pairs of form data
+///
+/// Created by [`parse`].
+#[derive(Clone, Debug)]
+pub struct Parse<'a> {
+    input: &'a str,
+}
+
+impl<'a> Iterator for Parse<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.input.is_empty() {
+                return None;
+            }
+
+            let (sequence, rest) = self.input.split_once('&').unwrap_or((self.input, ""));
+            self.input = rest;
+
+            // Empty sequences like in `a=1&&b=2` are skipped
+            if sequence.is_empty() {
+                continue;
+            }
+
+            let (name, value) = sequence.split_once('=').unwrap_or((sequence, ""));
+            return Some((decode_form_component(name), decode_form_component(value)));
+        }
+    }
+}
+
+impl FusedIterator for Parse<'_> {}
+
+/// Parses `application/x-www-form-urlencoded` data into name-value pairs.
+///
+/// Follows the [urlencoded parser](https://url.spec.whatwg.org/#concept-urlencoded-parser):
+/// the input is split on `&`, empty sequences are skipped and every sequence is split on its first `=`.
+/// A sequence without `=` yields an empty value.
+/// Names and values have `+` replaced by a space and get percent-decoded.
+/// Invalid UTF-8 is replaced by `U+FFFD`.
+///
+/// Names and values that contain nothing to decode are borrowed from the input.
+///
+/// # Arguments
+///
+/// * `input` - The form data, without a leading `?`.
+///
+/// # Returns
+///
+/// An iterator over the decoded `(name, value)` pairs.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::form;
+///
+/// let pairs: Vec<_> = form::parse("a=1&b=two+words&&c&d=x%3Dy=z").collect();
+/// assert_eq!(pairs, [("a".into(), "1".into()),
+///                    ("b".into(), "two words".into()),
+///                    ("c".into(), "".into()),
+///                    ("d".into(), "x=y=z".into())]);
+/// ```
+pub fn parse(input: &str) -> Parse<'_> {
+    Parse { input }
+}
+
+/// Replaces `+` by a space and percent-decodes the result.
+///
+/// Malformed escapes are kept as-is, just like [`decode`](crate::decode) keeps them.
+fn decode_form_component(component: &str) -> Cow<'_, str> {
+    if !component.bytes().any(|byte| byte == b'%' || byte == b'+') {
+        return Cow::Borrowed(component);
+    }
+
+    let decoded_bytes = decode_to_bytes_with_mode(component.as_bytes(), Mode::FormUrlEncoded);
+    Cow::Owned(String::from_utf8_lossy(&decoded_bytes).into_owned())
+}
+
+/// # Builds form data from name-value pairs
+///
+/// Names and values are encoded using [`EncodeSet::FORM_URLENCODED`] with spaces written as `+`,
+/// so `*`, `-`, `.` and `_` are the only characters besides ASCII alphanumerics that are left as-is.
+///
+/// The serializer writes into any [`fmt::Write`] target, like a `String` or a `Formatter`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::form::Serializer;
+///
+/// let mut serializer = Serializer::new(String::new());
+/// serializer.append_pair("name", "Jane Doe").unwrap();
+/// serializer.append_pair("tags", "a&b~c").unwrap();
+/// assert_eq!(serializer.finish(), "name=Jane+Doe&tags=a%26b%7Ec");
+///
+/// let mut serializer = Serializer::from_query("page=2");
+/// serializer.append_pair("sort", "asc").unwrap();
+/// assert_eq!(serializer.finish(), "page=2&sort=asc");
+/// ```
+#[derive(Debug)]
+pub struct Serializer<W: Write> {
+    target: W,
+    needs_separator: bool,
+    case: HexCase,
+}
+
+impl<W: Write> Serializer<W> {
+    /// Creates a serializer that writes into an empty `target`.
+    pub fn new(target: W) -> Self {
+        Serializer {
+            target,
+            needs_separator: false,
+            case: HexCase::Upper,
+        }
+    }
+
+    /// Creates a serializer for a `target` that already holds at least one pair,
+    /// so the first appended pair is preceded by `&`.
+    pub fn continuing(target: W) -> Self {
+        Serializer {
+            target,
+            needs_separator: true,
+            case: HexCase::Upper,
+        }
+    }
+
+    /// Sets the [`HexCase`] of the hex digits in escapes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use url_encor::form::Serializer;
+    /// use url_encor::HexCase;
+    ///
+    /// let mut serializer = Serializer::new(String::new()).hex_case(HexCase::Lower);
+    /// serializer.append_pair("path", "/ä").unwrap();
+    /// assert_eq!(serializer.finish(), "path=%2f%c3%a4");
+    /// ```
+    pub fn hex_case(mut self, case: HexCase) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Appends a `name=value` pair.
+    pub fn append_pair(&mut self, name: &str, value: &str) -> fmt::Result {
+        self.append_separator()?;
+        write_form_component(&mut self.target, name, self.case)?;
+        self.target.write_char('=')?;
+        write_form_component(&mut self.target, value, self.case)
+    }
+
+    /// Appends a name without a value and without `=`.
+    pub fn append_key_only(&mut self, name: &str) -> fmt::Result {
+        self.append_separator()?;
+        write_form_component(&mut self.target, name, self.case)
+    }
+
+    /// Appends every pair of `pairs`.
+    pub fn extend_pairs<I, K, V>(&mut self, pairs: I) -> fmt::Result
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for pair in pairs {
+            let (name, value) = pair.borrow();
+            self.append_pair(name.as_ref(), value.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns a reference to the target.
+    pub fn get_ref(&self) -> &W {
+        &self.target
+    }
+
+    /// Consumes the serializer and returns the target.
+    pub fn finish(self) -> W {
+        self.target
+    }
+
+    fn append_separator(&mut self) -> fmt::Result {
+        if self.needs_separator {
+            self.target.write_char('&')?;
+        }
+        self.needs_separator = true;
+
+        Ok(())
+    }
+}
+
+impl Serializer<String> {
+    /// Creates a serializer that appends to a copy of an existing, already encoded query string.
+    ///
+    /// A leading `?` is removed.
+    pub fn from_query(query: &str) -> Self {
+        let query = query.strip_prefix('?').unwrap_or(query);
+        Serializer {
+            target: query.to_string(),
+            needs_separator: !query.is_empty(),
+            case: HexCase::Upper,
+        }
+    }
+}
+
+/// Serializes name-value pairs into `application/x-www-form-urlencoded` data.
+///
+/// # Arguments
+///
+/// * `pairs` - The `(name, value)` pairs to serialize.
+///
+/// # Returns
+///
+/// A new `String` containing the encoded pairs.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::form;
+///
+/// assert_eq!(form::serialize([("q", "rust lang"), ("page", "1")]), "q=rust+lang&page=1");
+/// ```
+pub fn serialize<I, K, V>(pairs: I) -> String
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut serializer = Serializer::new(String::new());
+    // Writing into a `String` never fails
+    let _ = serializer.extend_pairs(pairs);
+
+    serializer.finish()
+}
+
+/// Writes a name or value encoded with the form-urlencoded set, spaces become `+`.
+fn write_form_component<W: Write>(target: &mut W, component: &str, case: HexCase) -> fmt::Result {
+    write!(target, "{}", display_with(component, &EncodeSet::FORM_URLENCODED).mode(Mode::FormUrlEncoded).hex_case(case))
+}
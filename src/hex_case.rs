@@ -0,0 +1,26 @@
+use crate::preprocessing::{HEX_DIGITS, HEX_DIGITS_LOWER};
+
+/// # Which hex digits escapes are written with
+///
+/// [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-2.1) recommends uppercase digits like `%2F`,
+/// which is what [`encode`](crate::encode) writes.
+/// Some legacy systems and signing schemes expect lowercase digits like `%2f` instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexCase {
+    /// Escapes are written like `%C3%A4`.
+    Upper,
+
+    /// Escapes are written like `%c3%a4`.
+    Lower,
+}
+
+impl HexCase {
+    /// Returns the table of hex digits for this case.
+    #[inline]
+    pub(crate) fn digits(self) -> &'static [u8; 16] {
+        match self {
+            HexCase::Upper => HEX_DIGITS,
+            HexCase::Lower => HEX_DIGITS_LOWER,
+        }
+    }
+}
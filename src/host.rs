@@ -0,0 +1,400 @@
+use std::fmt::{Display, Formatter};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use crate::{decode_to_bytes_with_mode, encode_bytes_into, idna, EncodeSet, HexCase, Mode, UrlError};
+
+/// # The host of a URL
+///
+/// The result of the [WHATWG host parser](https://url.spec.whatwg.org/#concept-host-parser).
+/// Hosts of special URLs like `http:` are domains or IP addresses, where IPv4 addresses may be
+/// written in the short, hexadecimal and octal forms browsers accept. Hosts of other URLs are opaque,
+/// unless they are IPv6 addresses.
+///
+/// The empty host of URLs like `file:///etc` is an empty [`Host::Domain`].
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// use url_encor::Host;
+///
+/// assert_eq!(Host::parse("127.1").unwrap(), Host::Ipv4(Ipv4Addr::LOCALHOST));
+/// assert_eq!(Host::parse("0x7f.1").unwrap(), Host::Ipv4(Ipv4Addr::LOCALHOST));
+/// assert_eq!(Host::parse("Example.COM").unwrap(), Host::Domain("example.com".to_string()));
+/// assert_eq!(Host::parse("[0:0::1]").unwrap().to_string(), "[::1]");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Host {
+    /// A lowercase ASCII domain, internationalized labels are `xn--` labels.
+    Domain(String),
+
+    /// An IPv4 address.
+    Ipv4(Ipv4Addr),
+
+    /// An IPv6 address, written in brackets in URLs.
+    Ipv6(Ipv6Addr),
+
+    /// The percent-encoded host of a non-special URL, like `host` in `ssh://host/`.
+    Opaque(String),
+}
+
+impl Host {
+    /// Parses the host of a special URL.
+    ///
+    /// The input is percent-decoded, converted with [`idna::domain_to_ascii`](crate::idna::domain_to_ascii)
+    /// and parsed as an IPv4 address if the last label is a number.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The host, IPv6 addresses in brackets.
+    ///
+    /// # Returns
+    ///
+    /// The parsed `Host`, or a [`UrlError`] describing why parsing failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    /// use url_encor::{Host, UrlError};
+    ///
+    /// assert_eq!(Host::parse("0300.0250.0.1").unwrap(), Host::Ipv4(Ipv4Addr::new(192, 168, 0, 1)));
+    /// assert_eq!(Host::parse("3232235521").unwrap(), Host::Ipv4(Ipv4Addr::new(192, 168, 0, 1)));
+    /// assert_eq!(Host::parse("1.2.3.256"), Err(UrlError::InvalidIpv4Address));
+    /// ```
+    pub fn parse(input: &str) -> Result<Host, UrlError> {
+        Host::parse_with(input, false)
+    }
+
+    /// Parses the host of a non-special URL.
+    ///
+    /// The input is only checked for forbidden code points and percent-encoded, it is not lowercased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use url_encor::Host;
+    ///
+    /// assert_eq!(Host::parse_opaque("Host\u{E9}").unwrap(), Host::Opaque("Host%C3%A9".to_string()));
+    /// assert_eq!(Host::parse_opaque("127.1").unwrap(), Host::Opaque("127.1".to_string()));
+    /// ```
+    pub fn parse_opaque(input: &str) -> Result<Host, UrlError> {
+        Host::parse_with(input, true)
+    }
+
+    pub(crate) fn parse_with(input: &str, is_opaque: bool) -> Result<Host, UrlError> {
+        if let Some(rest) = input.strip_prefix('[') {
+            let address = rest.strip_suffix(']').ok_or(UrlError::InvalidIpv6Address)?;
+            return parse_ipv6(address).map(Host::Ipv6);
+        }
+
+        if is_opaque {
+            if input.chars().any(is_forbidden_host_code_point) {
+                return Err(UrlError::InvalidHost);
+            }
+            let mut host = String::with_capacity(input.len());
+            encode_bytes_into(&mut host, input.as_bytes(), &EncodeSet::C0_CONTROL, Mode::Rfc3986, HexCase::Upper);
+            return Ok(Host::Opaque(host));
+        }
+
+        let domain = String::from_utf8_lossy(&decode_to_bytes_with_mode(input.as_bytes(), Mode::Rfc3986)).into_owned();
+        let ascii_domain = domain_to_ascii(&domain)?;
+        if ascii_domain.chars().any(is_forbidden_domain_code_point) {
+            return Err(UrlError::InvalidHost);
+        }
+
+        if ends_in_a_number(&ascii_domain) {
+            return parse_ipv4(&ascii_domain).map(Host::Ipv4);
+        }
+
+        Ok(Host::Domain(ascii_domain))
+    }
+}
+
+/// Serializes the host like it appears in a URL.
+///
+/// IPv6 addresses are enclosed in brackets and written in lowercase hex, compressing the first
+/// longest run of zero pieces. With the alternate flag `{:#}`, IPv4-mapped IPv6 addresses end in
+/// the dotted IPv4 form recommended by RFC 5952.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::Host;
+///
+/// let host = Host::parse("[::ffff:192.168.0.1]").unwrap();
+/// assert_eq!(host.to_string(), "[::ffff:c0a8:1]");
+/// assert_eq!(format!("{:#}", host), "[::ffff:192.168.0.1]");
+/// ```
+impl Display for Host {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Host::Domain(domain) => f.write_str(domain),
+            Host::Opaque(host) => f.write_str(host),
+            Host::Ipv4(address) => write!(f, "{}", address),
+            Host::Ipv6(address) => match address.to_ipv4_mapped() {
+                Some(ipv4) if f.alternate() => write!(f, "[::ffff:{}]", ipv4),
+                _ => write!(f, "[{}]", serialize_ipv6(*address)),
+            },
+        }
+    }
+}
+
+impl FromStr for Host {
+    type Err = UrlError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Host::parse(input)
+    }
+}
+
+/// Code points that are never allowed in a host.
+fn is_forbidden_host_code_point(c: char) -> bool {
+    matches!(
+        c,
+        '\0' | '\t' | '\n' | '\r' | ' ' | '#' | '/' | ':' | '<' | '>' | '?' | '@' | '[' | '\\' | ']' | '^' | '|'
+    )
+}
+
+/// Code points that are never allowed in a domain.
+fn is_forbidden_domain_code_point(c: char) -> bool {
+    is_forbidden_host_code_point(c) || c <= '\u{1F}' || c == '%' || c == '\u{7F}'
+}
+
+/// The [domain to ASCII](https://url.spec.whatwg.org/#concept-domain-to-ascii) algorithm, not strict.
+fn domain_to_ascii(domain: &str) -> Result<String, UrlError> {
+    let ascii_domain = idna::domain_to_ascii(domain).map_err(|_| UrlError::InvalidDomain)?;
+    if ascii_domain.is_empty() {
+        return Err(UrlError::InvalidDomain);
+    }
+
+    Ok(ascii_domain)
+}
+
+/// Returns `true` if the last label is a number, so the domain has to be an IPv4 address.
+fn ends_in_a_number(domain: &str) -> bool {
+    let domain = domain.strip_suffix('.').unwrap_or(domain);
+    let last = domain.rsplit('.').next().unwrap_or(domain);
+    if !last.is_empty() && last.bytes().all(|byte| byte.is_ascii_digit()) {
+        return true;
+    }
+
+    match last.strip_prefix("0x").or_else(|| last.strip_prefix("0X")) {
+        Some(hex) => hex.bytes().all(|byte| byte.is_ascii_hexdigit()),
+        None => false,
+    }
+}
+
+/// The [IPv4 parser](https://url.spec.whatwg.org/#concept-ipv4-parser).
+///
+/// Accepts one to four parts, each decimal, octal with a leading `0` or hexadecimal with a leading `0x`.
+/// The last part fills all remaining bytes, so `127.1` is `127.0.0.1`.
+fn parse_ipv4(input: &str) -> Result<Ipv4Addr, UrlError> {
+    let input = match input.strip_suffix('.') {
+        Some(rest) if !rest.is_empty() => rest,
+        _ => input,
+    };
+
+    let parts: Vec<&str> = input.split('.').collect();
+    if parts.len() > 4 {
+        return Err(UrlError::InvalidIpv4Address);
+    }
+
+    let numbers = parts
+        .iter()
+        .map(|part| parse_ipv4_number(part))
+        .collect::<Option<Vec<u64>>>()
+        .ok_or(UrlError::InvalidIpv4Address)?;
+    let (&last, leading) = numbers.split_last().ok_or(UrlError::InvalidIpv4Address)?;
+
+    if leading.iter().any(|&number| number > 255) || last >= 256u64.pow(5 - numbers.len() as u32) {
+        return Err(UrlError::InvalidIpv4Address);
+    }
+
+    let mut address = last;
+    for (index, &number) in leading.iter().enumerate() {
+        address += number << (8 * (3 - index));
+    }
+
+    Ok(Ipv4Addr::from(address as u32))
+}
+
+/// The [IPv4 number parser](https://url.spec.whatwg.org/#ipv4-number-parser), `None` on failure.
+///
+/// Numbers that don't fit into a `u64` fail as well, they are too large for an address anyway.
+fn parse_ipv4_number(input: &str) -> Option<u64> {
+    if input.is_empty() {
+        return None;
+    }
+
+    let (digits, radix) = if let Some(hex) = input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        (hex, 16)
+    } else if input.len() > 1 && input.starts_with('0') {
+        (&input[1..], 8)
+    } else {
+        (input, 10)
+    };
+
+    if digits.is_empty() {
+        return Some(0);
+    }
+    if !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+
+    u64::from_str_radix(digits, radix).ok()
+}
+
+/// The [IPv6 parser](https://url.spec.whatwg.org/#concept-ipv6-parser).
+///
+/// Accepts one `::` and a trailing dotted IPv4 address, like `::ffff:192.168.0.1`.
+pub(crate) fn parse_ipv6(input: &str) -> Result<Ipv6Addr, UrlError> {
+    let input = input.as_bytes();
+    let error = UrlError::InvalidIpv6Address;
+
+    let mut address = [0u16; 8];
+    let mut piece_index = 0;
+    let mut compress: Option<usize> = None;
+    let mut pointer = 0;
+
+    if input.first() == Some(&b':') {
+        if input.get(1) != Some(&b':') {
+            return Err(error);
+        }
+        pointer += 2;
+        piece_index += 1;
+        compress = Some(piece_index);
+    }
+
+    while pointer < input.len() {
+        if piece_index == 8 {
+            return Err(error);
+        }
+
+        if input[pointer] == b':' {
+            if compress.is_some() {
+                return Err(error);
+            }
+            pointer += 1;
+            piece_index += 1;
+            compress = Some(piece_index);
+            continue;
+        }
+
+        let mut value: u16 = 0;
+        let mut length = 0;
+        while length < 4 && pointer < input.len() && input[pointer].is_ascii_hexdigit() {
+            value = value * 0x10 + (input[pointer] as char).to_digit(16).unwrap_or(0) as u16;
+            pointer += 1;
+            length += 1;
+        }
+
+        match input.get(pointer) {
+            Some(b'.') => {
+                if length == 0 || piece_index > 6 {
+                    return Err(error);
+                }
+                pointer -= length;
+                parse_embedded_ipv4(&input[pointer..], &mut address[piece_index..piece_index + 2])?;
+                piece_index += 2;
+                break;
+            }
+            Some(b':') => {
+                pointer += 1;
+                if pointer == input.len() {
+                    return Err(error);
+                }
+            }
+            Some(_) => return Err(error),
+            None => {}
+        }
+
+        address[piece_index] = value;
+        piece_index += 1;
+    }
+
+    match compress {
+        Some(compress) => {
+            let mut swaps = piece_index - compress;
+            piece_index = 7;
+            while piece_index != 0 && swaps > 0 {
+                address.swap(piece_index, compress + swaps - 1);
+                piece_index -= 1;
+                swaps -= 1;
+            }
+        }
+        None if piece_index != 8 => return Err(error),
+        None => {}
+    }
+
+    Ok(Ipv6Addr::from(address))
+}
+
+/// Parses the dotted IPv4 address at the end of an IPv6 address into two pieces.
+///
+/// Every part has to be decimal, between `0` and `255` and without leading zeros.
+fn parse_embedded_ipv4(input: &[u8], pieces: &mut [u16]) -> Result<(), UrlError> {
+    let error = UrlError::InvalidIpv6Address;
+
+    let parts: Vec<&[u8]> = input.split(|&byte| byte == b'.').collect();
+    if parts.len() != 4 {
+        return Err(error);
+    }
+
+    let mut bytes = [0u8; 4];
+    for (byte, part) in bytes.iter_mut().zip(parts) {
+        let valid = !part.is_empty()
+            && part.iter().all(u8::is_ascii_digit)
+            && (part.len() == 1 || part[0] != b'0')
+            && part.len() <= 3;
+        let number: u16 = std::str::from_utf8(part).ok().filter(|_| valid).and_then(|part| part.parse().ok()).ok_or(error)?;
+        *byte = u8::try_from(number).map_err(|_| error)?;
+    }
+
+    pieces[0] = u16::from_be_bytes([bytes[0], bytes[1]]);
+    pieces[1] = u16::from_be_bytes([bytes[2], bytes[3]]);
+
+    Ok(())
+}
+
+/// Serializes an IPv6 address in lowercase hex, compressing the first longest run of zero pieces.
+// `Option::is_none_or` needs Rust 1.82
+#[allow(clippy::unnecessary_map_or)]
+fn serialize_ipv6(address: Ipv6Addr) -> String {
+    let pieces = address.segments();
+
+    let mut compress: Option<(usize, usize)> = None;
+    let mut index = 0;
+    while index < pieces.len() {
+        let start = index;
+        while index < pieces.len() && pieces[index] == 0 {
+            index += 1;
+        }
+        let length = index - start;
+        if length > 1 && compress.map_or(true, |(_, longest)| length > longest) {
+            compress = Some((start, length));
+        }
+        index += 1;
+    }
+
+    let mut output = String::new();
+    let mut index = 0;
+    while index < pieces.len() {
+        match compress {
+            Some((start, length)) if index == start => {
+                output.push_str(if index == 0 { "::" } else { ":" });
+                index += length;
+            }
+            _ => {
+                output.push_str(&format!("{:x}", pieces[index]));
+                if index != pieces.len() - 1 {
+                    output.push(':');
+                }
+                index += 1;
+            }
+        }
+    }
+
+    output
+}
@@ -0,0 +1,475 @@
+//! # Internationalized domain names
+//!
+//! Converts domains between their Unicode and ASCII forms following
+//! [UTS #46](https://www.unicode.org/reports/tr46/): every code point gets mapped, for example to
+//! lowercase, the result is normalized to NFC, split into labels and validated. Labels with non-ASCII
+//! characters are written as `xn--` followed by their [Punycode](crate::punycode).
+//!
+//! The mapping, bidi and normalization tables are generated by `build.rs` from the Unicode 15.1.0 data files
+//! in `data/`: the `IdnaMappingTable.txt` published with UTS #46, the `UnicodeData.txt` of the
+//! Unicode Character Database and the Joining_Type and composition exclusion properties.
+//!
+//! # Examples
+//!
+//! ```
+//! use url_encor::idna;
+//!
+//! assert_eq!(idna::domain_to_ascii("Bücher.example").unwrap(), "xn--bcher-kva.example");
+//! assert_eq!(idna::domain_to_unicode("xn--bcher-kva.example").unwrap(), "bücher.example");
+//! ```
+
+use crate::unicode_tables::{self, BidiClass, JoiningType, Status};
+use crate::{punycode, IdnaError};
+
+const ACE_PREFIX: &str = "xn--";
+
+const VIRAMA_COMBINING_CLASS: u8 = 9;
+const ZERO_WIDTH_NON_JOINER: char = '\u{200C}';
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+const HANGUL_S_BASE: u32 = 0xAC00;
+const HANGUL_L_BASE: u32 = 0x1100;
+const HANGUL_V_BASE: u32 = 0x1161;
+const HANGUL_T_BASE: u32 = 0x11A7;
+const HANGUL_L_COUNT: u32 = 19;
+const HANGUL_V_COUNT: u32 = 21;
+const HANGUL_T_COUNT: u32 = 28;
+const HANGUL_N_COUNT: u32 = HANGUL_V_COUNT * HANGUL_T_COUNT;
+const HANGUL_S_COUNT: u32 = HANGUL_L_COUNT * HANGUL_N_COUNT;
+
+/// # Processing options of UTS #46
+///
+/// The defaults match what browsers use for URLs: nontransitional processing, bidi and joiner
+/// checks enabled, everything else disabled.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::idna::IdnaOptions;
+///
+/// const STRICT: IdnaOptions = IdnaOptions::new().check_hyphens(true).use_std3_ascii_rules(true).verify_dns_length(true);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IdnaOptions {
+    check_hyphens: bool,
+    check_bidi: bool,
+    check_joiners: bool,
+    use_std3_ascii_rules: bool,
+    transitional_processing: bool,
+    verify_dns_length: bool,
+}
+
+impl IdnaOptions {
+    /// Creates the default options.
+    pub const fn new() -> Self {
+        IdnaOptions {
+            check_hyphens: false,
+            check_bidi: true,
+            check_joiners: true,
+            use_std3_ascii_rules: false,
+            transitional_processing: false,
+            verify_dns_length: false,
+        }
+    }
+
+    /// Rejects labels that start or end with `-` or have `--` at their third and fourth position.
+    pub const fn check_hyphens(mut self, check_hyphens: bool) -> Self {
+        self.check_hyphens = check_hyphens;
+        self
+    }
+
+    /// Applies the bidi rule of RFC 5893 to domains with right-to-left characters.
+    pub const fn check_bidi(mut self, check_bidi: bool) -> Self {
+        self.check_bidi = check_bidi;
+        self
+    }
+
+    /// Applies the [CONTEXTJ rules](https://www.rfc-editor.org/rfc/rfc5892#appendix-A.1) of RFC 5892:
+    /// zero width joiners have to follow a virama, zero width non-joiners have to follow a virama
+    /// or sit between two characters that join with it, like in Persian.
+    pub const fn check_joiners(mut self, check_joiners: bool) -> Self {
+        self.check_joiners = check_joiners;
+        self
+    }
+
+    /// Only accepts letters, digits and `-` from the ASCII range.
+    pub const fn use_std3_ascii_rules(mut self, use_std3_ascii_rules: bool) -> Self {
+        self.use_std3_ascii_rules = use_std3_ascii_rules;
+        self
+    }
+
+    /// Maps the deviation characters `ß`, `ς` and the zero width joiners the way IDNA 2003 did.
+    pub const fn transitional_processing(mut self, transitional_processing: bool) -> Self {
+        self.transitional_processing = transitional_processing;
+        self
+    }
+
+    /// Rejects ASCII domains longer than 253 bytes, labels longer than 63 bytes and empty labels.
+    ///
+    /// Only checked by [`domain_to_ascii_with`], a trailing `.` is allowed.
+    pub const fn verify_dns_length(mut self, verify_dns_length: bool) -> Self {
+        self.verify_dns_length = verify_dns_length;
+        self
+    }
+}
+
+impl Default for IdnaOptions {
+    fn default() -> Self {
+        IdnaOptions::new()
+    }
+}
+
+/// Converts a domain to its ASCII form using the default [`IdnaOptions`].
+///
+/// # Arguments
+///
+/// * `domain` - The domain, with labels in any case and form.
+///
+/// # Returns
+///
+/// The mapped domain with every non-ASCII label Punycode-encoded, or an [`IdnaError`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{idna, IdnaError};
+///
+/// assert_eq!(idna::domain_to_ascii("MÜNCHEN.de").unwrap(), "xn--mnchen-3ya.de");
+/// assert_eq!(idna::domain_to_ascii("faß.de").unwrap(), "xn--fa-hia.de");
+/// assert_eq!(idna::domain_to_ascii("a\u{E000}.com"), Err(IdnaError::DisallowedCharacter { c: '\u{E000}' }));
+/// ```
+pub fn domain_to_ascii(domain: &str) -> Result<String, IdnaError> {
+    domain_to_ascii_with(domain, &IdnaOptions::new())
+}
+
+/// Converts a domain to its ASCII form using custom [`IdnaOptions`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{idna::{self, IdnaOptions}, IdnaError};
+///
+/// let transitional = IdnaOptions::new().transitional_processing(true);
+/// assert_eq!(idna::domain_to_ascii_with("faß.de", &transitional).unwrap(), "fass.de");
+///
+/// let strict = IdnaOptions::new().check_hyphens(true);
+/// assert_eq!(idna::domain_to_ascii_with("-example.com", &strict), Err(IdnaError::InvalidHyphen));
+/// ```
+pub fn domain_to_ascii_with(domain: &str, options: &IdnaOptions) -> Result<String, IdnaError> {
+    let labels = process(domain, options)?;
+
+    let mut ascii_labels = Vec::with_capacity(labels.len());
+    for label in labels {
+        if label.is_ascii() {
+            ascii_labels.push(label);
+        } else {
+            let encoded = punycode::encode(&label).map_err(|_| IdnaError::InvalidPunycode)?;
+            ascii_labels.push(format!("{}{}", ACE_PREFIX, encoded));
+        }
+    }
+
+    if options.verify_dns_length {
+        verify_dns_length(&ascii_labels)?;
+    }
+
+    Ok(ascii_labels.join("."))
+}
+
+/// Converts a domain to its Unicode form using the default [`IdnaOptions`].
+///
+/// `xn--` labels get decoded, all other labels get mapped like in [`domain_to_ascii`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{idna, IdnaError};
+///
+/// assert_eq!(idna::domain_to_unicode("xn--mnchen-3ya.DE").unwrap(), "münchen.de");
+/// assert_eq!(idna::domain_to_unicode("xn--mnchen-3y!.de"), Err(IdnaError::InvalidPunycode));
+/// ```
+pub fn domain_to_unicode(domain: &str) -> Result<String, IdnaError> {
+    domain_to_unicode_with(domain, &IdnaOptions::new())
+}
+
+/// Converts a domain to its Unicode form using custom [`IdnaOptions`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::idna::{self, IdnaOptions};
+///
+/// let transitional = IdnaOptions::new().transitional_processing(true);
+/// assert_eq!(idna::domain_to_unicode_with("Faß.de", &transitional).unwrap(), "fass.de");
+/// ```
+pub fn domain_to_unicode_with(domain: &str, options: &IdnaOptions) -> Result<String, IdnaError> {
+    Ok(process(domain, options)?.join("."))
+}
+
+/// The [processing steps](https://www.unicode.org/reports/tr46/#Processing), returns the validated Unicode labels.
+fn process(domain: &str, options: &IdnaOptions) -> Result<Vec<String>, IdnaError> {
+    let mapped = normalize_nfc(&map(domain, options)?);
+
+    let mut labels = Vec::new();
+    for label in mapped.split('.') {
+        match label.strip_prefix(ACE_PREFIX) {
+            Some(encoded) => {
+                if !encoded.is_ascii() {
+                    return Err(IdnaError::InvalidPunycode);
+                }
+                let decoded = punycode::decode(encoded).map_err(|_| IdnaError::InvalidPunycode)?;
+                if decoded.is_empty() || decoded.is_ascii() {
+                    return Err(IdnaError::InvalidPunycode);
+                }
+                // A decoded code point that isn't valid means the label was never valid Punycode output
+                validate_label(&decoded, options, false).map_err(|error| match error {
+                    IdnaError::DisallowedCharacter { .. } => IdnaError::InvalidPunycode,
+                    error => error,
+                })?;
+                labels.push(decoded);
+            }
+            None => {
+                validate_label(label, options, options.transitional_processing)?;
+                labels.push(label.to_string());
+            }
+        }
+    }
+
+    if options.check_bidi && labels.iter().any(|label| is_bidi_label(label)) {
+        for label in &labels {
+            check_bidi(label)?;
+        }
+    }
+
+    Ok(labels)
+}
+
+/// Maps every code point according to its IDNA status.
+fn map(domain: &str, options: &IdnaOptions) -> Result<String, IdnaError> {
+    let mut mapped = String::with_capacity(domain.len());
+
+    for c in domain.chars() {
+        let (status, mapping) = unicode_tables::idna_mapping(c);
+        match status {
+            Status::Valid => mapped.push(c),
+            Status::Ignored => {}
+            // UTS #46 keeps mapping ẞ to "ss" in transitional processing, its table maps it to ß
+            Status::Mapped if c == '\u{1E9E}' && options.transitional_processing => mapped.push_str("ss"),
+            Status::Mapped => mapped.extend(mapping),
+            Status::Deviation if options.transitional_processing => mapped.extend(mapping),
+            Status::Deviation => mapped.push(c),
+            // The STD3 rules are checked after normalization, `=` and U+0338 compose to the valid `≠`
+            Status::DisallowedStd3Valid => mapped.push(c),
+            Status::DisallowedStd3Mapped => mapped.extend(mapping),
+            Status::Disallowed => return Err(IdnaError::DisallowedCharacter { c }),
+        }
+    }
+
+    Ok(mapped)
+}
+
+/// The [validity criteria](https://www.unicode.org/reports/tr46/#Validity_Criteria) of a single label.
+fn validate_label(label: &str, options: &IdnaOptions, transitional: bool) -> Result<(), IdnaError> {
+    if normalize_nfc(label) != label {
+        return Err(IdnaError::NotNormalized);
+    }
+
+    if options.check_hyphens {
+        if label.starts_with('-') || label.ends_with('-') || label.get(2..4) == Some("--") {
+            return Err(IdnaError::InvalidHyphen);
+        }
+    } else if label.starts_with(ACE_PREFIX) {
+        return Err(IdnaError::InvalidHyphen);
+    }
+
+    if label.chars().next().is_some_and(unicode_tables::is_mark) {
+        return Err(IdnaError::LeadingCombiningMark);
+    }
+
+    for c in label.chars() {
+        let valid = match unicode_tables::idna_mapping(c).0 {
+            Status::Valid => c != '.',
+            Status::Deviation => !transitional,
+            Status::DisallowedStd3Valid => !options.use_std3_ascii_rules,
+            _ => false,
+        };
+        if !valid {
+            return Err(IdnaError::DisallowedCharacter { c });
+        }
+    }
+
+    if options.check_joiners {
+        let mut previous: Option<char> = None;
+        for (index, c) in label.char_indices() {
+            if matches!(c, ZERO_WIDTH_NON_JOINER | ZERO_WIDTH_JOINER) {
+                let after_virama = previous.map(unicode_tables::combining_class) == Some(VIRAMA_COMBINING_CLASS);
+                let valid = after_virama
+                    || (c == ZERO_WIDTH_NON_JOINER && is_joining_context(&label[..index], &label[index + c.len_utf8()..]));
+                if !valid {
+                    return Err(IdnaError::InvalidJoiner);
+                }
+            }
+            previous = Some(c);
+        }
+    }
+
+    Ok(())
+}
+
+/// Matches `(Joining_Type:{L,D})(Joining_Type:T)*` against the end of `before`
+/// and `(Joining_Type:T)*(Joining_Type:{R,D})` against the start of `after`.
+fn is_joining_context(before: &str, after: &str) -> bool {
+    let not_transparent = |c: char| {
+        let joining_type = unicode_tables::joining_type(c);
+        (joining_type != Some(JoiningType::T)).then_some(joining_type)
+    };
+
+    matches!(before.chars().rev().find_map(not_transparent), Some(Some(JoiningType::L | JoiningType::D)))
+        && matches!(after.chars().find_map(not_transparent), Some(Some(JoiningType::R | JoiningType::D)))
+}
+
+/// Returns `true` if the label makes the domain a bidi domain.
+fn is_bidi_label(label: &str) -> bool {
+    label
+        .chars()
+        .any(|c| matches!(unicode_tables::bidi_class(c), BidiClass::R | BidiClass::AL | BidiClass::AN))
+}
+
+/// The [bidi rule](https://www.rfc-editor.org/rfc/rfc5893#section-2) for a label of a bidi domain.
+fn check_bidi(label: &str) -> Result<(), IdnaError> {
+    use BidiClass::*;
+
+    let classes: Vec<BidiClass> = label.chars().map(unicode_tables::bidi_class).collect();
+    let Some(&first) = classes.first() else {
+        return Ok(());
+    };
+    let last = classes.iter().rev().find(|&&class| class != NSM).copied();
+
+    let valid = match first {
+        R | AL => {
+            classes
+                .iter()
+                .all(|class| matches!(class, R | AL | AN | EN | ES | CS | ET | ON | BN | NSM))
+                && matches!(last, Some(R | AL | EN | AN))
+                && !(classes.contains(&EN) && classes.contains(&AN))
+        }
+        L => {
+            classes
+                .iter()
+                .all(|class| matches!(class, L | EN | ES | CS | ET | ON | BN | NSM))
+                && matches!(last, Some(L | EN))
+        }
+        _ => false,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(IdnaError::BidiRule)
+    }
+}
+
+fn verify_dns_length(labels: &[String]) -> Result<(), IdnaError> {
+    let labels = match labels.split_last() {
+        Some((last, rest)) if last.is_empty() && !rest.is_empty() => rest,
+        _ => labels,
+    };
+
+    let length = labels.iter().map(|label| label.len() + 1).sum::<usize>() - 1;
+    if length > 253 || labels.iter().any(|label| label.is_empty() || label.len() > 63) {
+        return Err(IdnaError::InvalidLength);
+    }
+
+    Ok(())
+}
+
+/// Normalizes to [NFC](https://www.unicode.org/reports/tr15/): canonical decomposition,
+/// canonical ordering, then canonical composition.
+fn normalize_nfc(input: &str) -> String {
+    let mut decomposed = Vec::with_capacity(input.len());
+    for c in input.chars() {
+        decompose(c, &mut decomposed);
+    }
+
+    // Sorts every run of non-starters by combining class, keeping the order of equal classes.
+    let mut start = 0;
+    while start < decomposed.len() {
+        if unicode_tables::combining_class(decomposed[start]) == 0 {
+            start += 1;
+            continue;
+        }
+        let mut end = start;
+        while end < decomposed.len() && unicode_tables::combining_class(decomposed[end]) != 0 {
+            end += 1;
+        }
+        decomposed[start..end].sort_by_key(|&c| unicode_tables::combining_class(c));
+        start = end;
+    }
+
+    let mut composed: Vec<char> = Vec::with_capacity(decomposed.len());
+    let mut starter: Option<usize> = None;
+    let mut last_class = 0;
+    for c in decomposed {
+        let class = unicode_tables::combining_class(c);
+        if let Some(index) = starter {
+            let adjacent = composed.len() == index + 1;
+            if adjacent || last_class < class {
+                if let Some(composite) = compose(composed[index], c) {
+                    composed[index] = composite;
+                    continue;
+                }
+            }
+        }
+        if class == 0 {
+            starter = Some(composed.len());
+        }
+        last_class = class;
+        composed.push(c);
+    }
+
+    composed.into_iter().collect()
+}
+
+fn decompose(c: char, output: &mut Vec<char>) {
+    let code_point = c as u32;
+    if (HANGUL_S_BASE..HANGUL_S_BASE + HANGUL_S_COUNT).contains(&code_point) {
+        let index = code_point - HANGUL_S_BASE;
+        let hangul = |code_point| char::from_u32(code_point).expect("Hangul jamo are valid code points");
+        output.push(hangul(HANGUL_L_BASE + index / HANGUL_N_COUNT));
+        output.push(hangul(HANGUL_V_BASE + (index % HANGUL_N_COUNT) / HANGUL_T_COUNT));
+        if !index.is_multiple_of(HANGUL_T_COUNT) {
+            output.push(hangul(HANGUL_T_BASE + index % HANGUL_T_COUNT));
+        }
+        return;
+    }
+
+    match unicode_tables::decomposition(c) {
+        Some((first, second)) => {
+            decompose(first, output);
+            if let Some(second) = second {
+                decompose(second, output);
+            }
+        }
+        None => output.push(c),
+    }
+}
+
+fn compose(first: char, second: char) -> Option<char> {
+    let (first_code_point, second_code_point) = (first as u32, second as u32);
+
+    if (HANGUL_L_BASE..HANGUL_L_BASE + HANGUL_L_COUNT).contains(&first_code_point)
+        && (HANGUL_V_BASE..HANGUL_V_BASE + HANGUL_V_COUNT).contains(&second_code_point)
+    {
+        let index = (first_code_point - HANGUL_L_BASE) * HANGUL_N_COUNT + (second_code_point - HANGUL_V_BASE) * HANGUL_T_COUNT;
+        return char::from_u32(HANGUL_S_BASE + index);
+    }
+
+    let syllable = first_code_point.wrapping_sub(HANGUL_S_BASE);
+    if syllable < HANGUL_S_COUNT
+        && syllable.is_multiple_of(HANGUL_T_COUNT)
+        && (HANGUL_T_BASE + 1..HANGUL_T_BASE + HANGUL_T_COUNT).contains(&second_code_point)
+    {
+        return char::from_u32(first_code_point + second_code_point - HANGUL_T_BASE);
+    }
+
+    unicode_tables::composition(first, second)
+}
@@ -1,4 +1,4 @@
-use crate::{Encoder, encode, decode};
+use crate::{Encoder, HexCase, Mode, encode, decode, encode_with_mode, decode_with_mode, encode_with_case};
 /// Implementation of the `Encoder` trait for `String`.
 impl Encoder for String {
     fn url_encode(&self) -> String {
@@ -8,6 +8,18 @@ impl Encoder for String {
     fn url_decode(&self) -> String {
         decode(self)
     }
+
+    fn url_encode_with_mode(&self, mode: Mode) -> String {
+        encode_with_mode(self, mode)
+    }
+
+    fn url_decode_with_mode(&self, mode: Mode) -> String {
+        decode_with_mode(self, mode)
+    }
+
+    fn url_encode_with_case(&self, case: HexCase) -> String {
+        encode_with_case(self, case)
+    }
 }
 
 impl Encoder for &str {
@@ -18,10 +30,25 @@ impl Encoder for &str {
     fn url_decode(&self) -> String {
         decode(self)
     }
+
+    fn url_encode_with_mode(&self, mode: Mode) -> String {
+        encode_with_mode(self, mode)
+    }
+
+    fn url_decode_with_mode(&self, mode: Mode) -> String {
+        decode_with_mode(self, mode)
+    }
+
+    fn url_encode_with_case(&self, case: HexCase) -> String {
+        encode_with_case(self, case)
+    }
 }
 
 
 
+/// Implementation of the `Encoder` trait for `Vec<String>`, every element gets encoded or decoded on its own.
+///
+/// `url_decode` decodes the elements. Up to version 1.0.3 it encoded them instead.
 impl Encoder<Vec<String>> for Vec<String> {
     fn url_encode(&self) -> Vec<String> {
         self.iter().map(|element| {
@@ -31,7 +58,25 @@ impl Encoder<Vec<String>> for Vec<String> {
 
     fn url_decode(&self) -> Vec<String> {
         self.iter().map(|element| {
-            encode(element)
+            decode(element)
+        }).collect()
+    }
+
+    fn url_encode_with_mode(&self, mode: Mode) -> Vec<String> {
+        self.iter().map(|element| {
+            encode_with_mode(element, mode)
+        }).collect()
+    }
+
+    fn url_decode_with_mode(&self, mode: Mode) -> Vec<String> {
+        self.iter().map(|element| {
+            decode_with_mode(element, mode)
+        }).collect()
+    }
+
+    fn url_encode_with_case(&self, case: HexCase) -> Vec<String> {
+        self.iter().map(|element| {
+            encode_with_case(element, case)
         }).collect()
     }
 }
@@ -0,0 +1,203 @@
+//! # Streaming encoding and decoding
+//!
+//! Adapters that encode or decode data while it flows through [`std::io::Write`] and [`std::io::Read`],
+//! so large payloads never have to be buffered as a whole.
+
+use std::io::{self, Read, Write};
+
+use crate::{decode_bytes_into, encode_bytes_into, EncodeSet, HexCase, Mode};
+
+/// Size of the chunks read from the inner reader.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// # Encodes everything written to it
+///
+/// Every byte written to an `EncodingWriter` is encoded and then passed on to the inner writer.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use url_encor::io::EncodingWriter;
+///
+/// let mut writer = EncodingWriter::new(Vec::new());
+/// writer.write_all(b"Hello, ").unwrap();
+/// writer.write_all("World!".as_bytes()).unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"Hello%2C%20World%21");
+/// ```
+#[derive(Debug)]
+pub struct EncodingWriter<W: Write> {
+    inner: W,
+    set: EncodeSet,
+    mode: Mode,
+    case: HexCase,
+    buffer: String,
+}
+
+impl<W: Write> EncodingWriter<W> {
+    /// Creates a writer that encodes like [`encode`](crate::encode).
+    pub fn new(inner: W) -> Self {
+        EncodingWriter::with_set(inner, &EncodeSet::DEFAULT)
+    }
+
+    /// Creates a writer that encodes every byte contained in `set`.
+    pub fn with_set(inner: W, set: &EncodeSet) -> Self {
+        EncodingWriter {
+            inner,
+            set: *set,
+            mode: Mode::Rfc3986,
+            case: HexCase::Upper,
+            buffer: String::new(),
+        }
+    }
+
+    /// Sets the [`Mode`] that decides how spaces get encoded.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the [`HexCase`] of the hex digits in escapes.
+    pub fn hex_case(mut self, case: HexCase) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `EncodingWriter`, returning the inner writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for EncodingWriter<W> {
+    /// Encodes the whole buffer and writes it to the inner writer.
+    ///
+    /// If the inner writer fails, an unknown part of the encoded buffer may already have been written.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.clear();
+        encode_bytes_into(&mut self.buffer, buf, &self.set, self.mode, self.case);
+        self.inner.write_all(self.buffer.as_bytes())?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// # Decodes everything read from it
+///
+/// Reads URL encoded data from the inner reader and hands out the decoded bytes.
+/// Escapes that are split across two reads of the inner reader are decoded correctly.
+///
+/// Decodes like [`decode_to_bytes`](crate::decode_to_bytes), so `+` becomes a space
+/// unless another [`Mode`] is selected.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use url_encor::io::DecodingReader;
+///
+/// let mut decoded = String::new();
+/// DecodingReader::new("Hello%2C+World%21".as_bytes()).read_to_string(&mut decoded).unwrap();
+///
+/// assert_eq!(decoded, "Hello, World!");
+/// ```
+#[derive(Debug)]
+pub struct DecodingReader<R: Read> {
+    inner: R,
+    mode: Mode,
+    /// Encoded bytes that could not be decoded yet, at most an incomplete escape
+    pending: Vec<u8>,
+    decoded: Vec<u8>,
+    decoded_position: usize,
+    reached_end: bool,
+}
+
+impl<R: Read> DecodingReader<R> {
+    /// Creates a reader that decodes like [`decode_to_bytes`](crate::decode_to_bytes).
+    pub fn new(inner: R) -> Self {
+        DecodingReader::with_mode(inner, Mode::FormUrlEncoded)
+    }
+
+    /// Creates a reader that decodes using the given [`Mode`].
+    pub fn with_mode(inner: R, mode: Mode) -> Self {
+        DecodingReader {
+            inner,
+            mode,
+            pending: Vec::new(),
+            decoded: Vec::new(),
+            decoded_position: 0,
+            reached_end: false,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `DecodingReader`, returning the inner reader.
+    ///
+    /// Data that was already read from the inner reader but not handed out yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the next chunk from the inner reader and decodes it.
+    fn fill_decoded(&mut self) -> io::Result<()> {
+        let mut chunk = [0u8; CHUNK_SIZE];
+        let read = self.inner.read(&mut chunk)?;
+        if read == 0 {
+            self.reached_end = true;
+        }
+        self.pending.extend_from_slice(&chunk[..read]);
+
+        self.decoded.clear();
+        self.decoded_position = 0;
+        let consumed = decode_bytes_into(&mut self.decoded, &self.pending, self.mode, self.reached_end);
+        self.pending.drain(..consumed);
+
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for DecodingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while self.decoded_position == self.decoded.len() {
+            if self.reached_end {
+                return Ok(0);
+            }
+            self.fill_decoded()?;
+        }
+
+        let available = &self.decoded[self.decoded_position..];
+        let length = available.len().min(buf.len());
+        buf[..length].copy_from_slice(&available[..length]);
+        self.decoded_position += length;
+
+        Ok(length)
+    }
+}
@@ -106,9 +106,48 @@
 
 mod preprocessing;
 mod implementations;
+mod encode_set;
+mod error;
+mod mode;
+mod hex_case;
+mod encode_options;
+mod display;
+pub mod io;
+pub mod form;
+pub mod nested;
+pub mod sigv4;
+pub mod oauth1;
+pub mod ext_value;
+pub mod punycode;
+pub mod idna;
+mod unicode_tables;
+mod url;
+mod uri;
+mod host;
+#[cfg(feature = "serde")]
+mod ser;
+#[cfg(feature = "serde")]
+mod de;
 
+use std::borrow::Cow;
 use preprocessing::{HEX_DIGITS, PREPROCESSED_ARRAY, HEX_BYTE_TO_HEX_VALUE};
 
+pub use encode_set::EncodeSet;
+pub use error::{DecodeError, ExtValueError, IdnaError, NestedError, PunycodeError, UriError, UrlError};
+#[cfg(feature = "serde")]
+pub use error::SerdeError;
+#[cfg(feature = "serde")]
+pub use ser::to_string;
+#[cfg(feature = "serde")]
+pub use de::from_str;
+pub use mode::Mode;
+pub use hex_case::HexCase;
+pub use encode_options::EncodeOptions;
+pub use url::Url;
+pub use host::Host;
+pub use uri::{Uri, resolve, remove_dot_segments, normalize, equivalent};
+pub use display::{display, display_with, decode_chunks, decode_chunks_with_mode, PercentEncoded, PercentDecoded};
+
 /// Encodes a string using url_encor.
 ///
 /// This function iterates through each byte of the input string and encodes it if necessary.
@@ -131,21 +170,351 @@ use preprocessing::{HEX_DIGITS, PREPROCESSED_ARRAY, HEX_BYTE_TO_HEX_VALUE};
 /// assert_eq!(encoded, "Hello%2C%20World%21");
 /// ```
 pub fn encode(str_to_encode: &str) -> String {
-    let mut encoded_string = String::with_capacity(str_to_encode.len() * 3);
+    encode_with(str_to_encode, &EncodeSet::DEFAULT)
+}
+
+/// Encodes a string using a custom [`EncodeSet`].
+///
+/// Every byte contained in `set` gets percent-encoded, every other byte is left as-is.
+/// This makes it possible to encode path segments, query values and fragments differently.
+///
+/// # Arguments
+///
+/// * `str_to_encode` - A string slice that holds the text to be URL encoded.
+/// * `set` - The set of bytes that should get encoded.
+///
+/// # Returns
+///
+/// A new `String` containing the URL encoded text.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{encode_with, EncodeSet};
+///
+/// let set = EncodeSet::DEFAULT.remove(b'/').add(b'~');
+/// assert_eq!(encode_with("/home/~user/my file", &set), "/home/%7Euser/my%20file");
+/// ```
+pub fn encode_with(str_to_encode: &str, set: &EncodeSet) -> String {
+    encode_with_options(str_to_encode, &EncodeOptions::new().set(*set))
+}
 
-    for &current_byte in str_to_encode.as_bytes() {
-        if PREPROCESSED_ARRAY[current_byte as usize] == 0 {
+/// Encodes a string using the given [`Mode`].
+///
+/// In [`Mode::FormUrlEncoded`] spaces are written as `+`, everything else is encoded like [`encode`].
+///
+/// # Arguments
+///
+/// * `str_to_encode` - A string slice that holds the text to be URL encoded.
+/// * `mode` - Decides how spaces get encoded.
+///
+/// # Returns
+///
+/// A new `String` containing the URL encoded text.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{encode_with_mode, Mode};
+///
+/// assert_eq!(encode_with_mode("c++ docs", Mode::Rfc3986), "c%2B%2B%20docs");
+/// assert_eq!(encode_with_mode("c++ docs", Mode::FormUrlEncoded), "c%2B%2B+docs");
+/// ```
+pub fn encode_with_mode(str_to_encode: &str, mode: Mode) -> String {
+    encode_with_options(str_to_encode, &EncodeOptions::new().mode(mode))
+}
+
+/// Encodes arbitrary bytes using url_encor.
+///
+/// Unlike [`encode`] the input does not have to be valid UTF-8,
+/// which makes it possible to encode binary payloads like hashes.
+///
+/// # Arguments
+///
+/// * `bytes_to_encode` - A byte slice that holds the data to be URL encoded.
+///
+/// # Returns
+///
+/// A new `String` containing the URL encoded data.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::encode_bytes;
+///
+/// let encoded = encode_bytes(&[0x12, 0x34, b'a', 0xFF]);
+/// assert_eq!(encoded, "%124a%FF");
+/// ```
+pub fn encode_bytes(bytes_to_encode: &[u8]) -> String {
+    encode_bytes_with_options(bytes_to_encode, &EncodeOptions::new())
+}
+
+/// Encodes a string like [`encode`], writing the escapes with the given [`HexCase`].
+///
+/// Combine a custom [`EncodeSet`], [`Mode`] and [`HexCase`] with [`encode_with_options`].
+///
+/// # Arguments
+///
+/// * `str_to_encode` - A string slice that holds the text to be URL encoded.
+/// * `case` - Decides whether the hex digits are uppercase or lowercase.
+///
+/// # Returns
+///
+/// A new `String` containing the URL encoded text.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{encode_with_case, HexCase};
+///
+/// assert_eq!(encode_with_case("a/ä", HexCase::Lower), "a%2f%c3%a4");
+/// assert_eq!(encode_with_case("a/ä", HexCase::Upper), "a%2F%C3%A4");
+/// ```
+pub fn encode_with_case(str_to_encode: &str, case: HexCase) -> String {
+    encode_with_options(str_to_encode, &EncodeOptions::new().hex_case(case))
+}
+
+/// Encodes arbitrary bytes like [`encode_bytes`], writing the escapes with the given [`HexCase`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{encode_bytes_with_case, HexCase};
+///
+/// assert_eq!(encode_bytes_with_case(&[0xAB, b'a', 0xFF], HexCase::Lower), "%aba%ff");
+/// ```
+pub fn encode_bytes_with_case(bytes_to_encode: &[u8], case: HexCase) -> String {
+    encode_bytes_with_options(bytes_to_encode, &EncodeOptions::new().hex_case(case))
+}
+
+/// Encodes a string using the [`EncodeSet`], [`Mode`] and [`HexCase`] of `options`.
+///
+/// # Arguments
+///
+/// * `str_to_encode` - A string slice that holds the text to be URL encoded.
+/// * `options` - Decides which bytes get encoded and how.
+///
+/// # Returns
+///
+/// A new `String` containing the URL encoded text.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{encode_with_options, EncodeOptions, EncodeSet, HexCase};
+///
+/// let options = EncodeOptions::new().set(EncodeSet::PATH).hex_case(HexCase::Lower);
+/// assert_eq!(encode_with_options("/a b/ä", &options), "/a%20b/%c3%a4");
+/// ```
+pub fn encode_with_options(str_to_encode: &str, options: &EncodeOptions) -> String {
+    encode_bytes_with_options(str_to_encode.as_bytes(), options)
+}
+
+/// Encodes arbitrary bytes using the [`EncodeSet`], [`Mode`] and [`HexCase`] of `options`.
+///
+/// Non-ASCII bytes are always part of an [`EncodeSet`], so the output is always valid UTF-8.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{encode_bytes_with_options, EncodeOptions, HexCase, Mode};
+///
+/// let options = EncodeOptions::new().mode(Mode::FormUrlEncoded).hex_case(HexCase::Lower);
+/// assert_eq!(encode_bytes_with_options(&[b' ', 0xAB], &options), "+%ab");
+/// ```
+pub fn encode_bytes_with_options(bytes_to_encode: &[u8], options: &EncodeOptions) -> String {
+    let mut encoded_string = String::with_capacity(bytes_to_encode.len() * 3);
+    encode_bytes_into(&mut encoded_string, bytes_to_encode, &options.set, options.mode, options.case);
+
+    encoded_string
+}
+
+/// Appends the encoded bytes to `encoded_string`, escaping every byte contained in `set`.
+pub(crate) fn encode_bytes_into(encoded_string: &mut String, bytes_to_encode: &[u8], set: &EncodeSet, mode: Mode, case: HexCase) {
+    let hex_digits = case.digits();
+    for &current_byte in bytes_to_encode {
+        if current_byte == b' ' && mode.space_as_plus() {
+            encoded_string.push('+');
+        } else if set.contains(current_byte) {
             // If the byte needs encoding, add a percent sign followed by two hex digits
             encoded_string.push('%');
-            encoded_string.push(HEX_DIGITS[(current_byte >> 4) as usize] as char);
-            encoded_string.push(HEX_DIGITS[(current_byte & 0xF) as usize] as char);
+            encoded_string.push(hex_digits[(current_byte >> 4) as usize] as char);
+            encoded_string.push(hex_digits[(current_byte & 0xF) as usize] as char);
         } else {
             // If the byte doesn't need encoding, add it as-is
             encoded_string.push(current_byte as char)
         }
     }
+}
 
-    encoded_string
+/// Encodes a string using url_encor, borrowing the input when nothing needs to be encoded.
+///
+/// Produces the same text as [`encode`], but only allocates if the input
+/// contains at least one character that has to be escaped.
+///
+/// # Arguments
+///
+/// * `str_to_encode` - A string slice that holds the text to be URL encoded.
+///
+/// # Returns
+///
+/// A `Cow::Borrowed` of the input if it is already clean, otherwise a `Cow::Owned` with the encoded text.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use url_encor::encode_cow;
+///
+/// assert!(matches!(encode_cow("user_42"), Cow::Borrowed("user_42")));
+/// assert_eq!(encode_cow("user 42"), "user%2042");
+/// ```
+pub fn encode_cow(str_to_encode: &str) -> Cow<'_, str> {
+    encode_cow_with_options(str_to_encode, &EncodeOptions::new())
+}
+
+/// Encodes a string like [`encode_with_options`], borrowing the input when nothing needs to be encoded.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use url_encor::{encode_cow_with_options, EncodeOptions, HexCase, Mode};
+///
+/// let options = EncodeOptions::new().mode(Mode::FormUrlEncoded).hex_case(HexCase::Lower);
+/// assert!(matches!(encode_cow_with_options("user_42", &options), Cow::Borrowed("user_42")));
+/// assert_eq!(encode_cow_with_options("user 42/ä", &options), "user+42%2f%c3%a4");
+/// ```
+pub fn encode_cow_with_options<'a>(str_to_encode: &'a str, options: &EncodeOptions) -> Cow<'a, str> {
+    let bytes = str_to_encode.as_bytes();
+    let needs_encoding = |byte: u8| options.set.contains(byte) || (byte == b' ' && options.mode.space_as_plus());
+    match bytes.iter().position(|&byte| needs_encoding(byte)) {
+        None => Cow::Borrowed(str_to_encode),
+        Some(first_to_encode) => {
+            let mut encoded_string = String::with_capacity(first_to_encode + (bytes.len() - first_to_encode) * 3);
+            encoded_string.push_str(&str_to_encode[..first_to_encode]);
+            encode_bytes_into(&mut encoded_string, &bytes[first_to_encode..], &options.set, options.mode, options.case);
+            Cow::Owned(encoded_string)
+        }
+    }
+}
+
+/// Normalizes the percent-encoding of an already encoded string, borrowing the input when it is already normalized.
+///
+/// Uses [`EncodeSet::DEFAULT`], see [`normalize_percent_encoding_with`].
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use url_encor::normalize_percent_encoding;
+///
+/// assert_eq!(normalize_percent_encoding("a%2fb%7E c%"), "a%2Fb~%20c%25");
+/// assert!(matches!(normalize_percent_encoding("a%2Fb~c"), Cow::Borrowed("a%2Fb~c")));
+/// ```
+pub fn normalize_percent_encoding(str_to_normalize: &str) -> Cow<'_, str> {
+    normalize_percent_encoding_with(str_to_normalize, &EncodeSet::DEFAULT)
+}
+
+/// Normalizes the percent-encoding of an already encoded string, borrowing the input when it is already normalized.
+///
+/// Two parties that encode the same text with the same set agree on every byte afterwards:
+///
+/// - Escapes get uppercase hex digits, like [`encode`] writes them.
+/// - Escapes of unreserved characters that are not part of `set` are decoded.
+/// - Raw characters that are part of `set` get encoded.
+///   A `%` that does not start a valid escape is encoded as `%25`, if `%` is part of `set`.
+///
+/// Escapes of other characters are kept, so `%2F` doesn't turn into a `/` that changes the meaning of a path.
+///
+/// # Arguments
+///
+/// * `str_to_normalize` - A string slice that holds percent-encoded text.
+/// * `set` - The characters that have to be encoded.
+///
+/// # Returns
+///
+/// A `Cow::Borrowed` of the input if it is already normalized, otherwise a `Cow::Owned` with the normalized text.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{normalize_percent_encoding_with, EncodeSet};
+///
+/// assert_eq!(normalize_percent_encoding_with("/a b/%2f/%41", &EncodeSet::PATH), "/a%20b/%2F/A");
+/// ```
+pub fn normalize_percent_encoding_with<'a>(str_to_normalize: &'a str, set: &EncodeSet) -> Cow<'a, str> {
+    normalize_escapes(str_to_normalize, Some(set))
+}
+
+/// Normalizes escapes and, if a set is given, encodes the raw characters that are part of it.
+///
+/// Without a set, every escape of an unreserved character is decoded and raw characters are kept,
+/// this is the normalization of RFC 3986, section 6.2.2.
+pub(crate) fn normalize_escapes<'a>(str_to_normalize: &'a str, set: Option<&EncodeSet>) -> Cow<'a, str> {
+    let bytes = str_to_normalize.as_bytes();
+    let mut normalized = String::new();
+    let mut unchanged_start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        let escape = match (bytes.get(index + 1), bytes.get(index + 2)) {
+            (Some(&high), Some(&low)) if byte == b'%' => from_hex(high).zip(from_hex(low)).map(|hex| (hex, high, low)),
+            _ => None,
+        };
+
+        let replaced_length = match escape {
+            Some(((high, low), high_digit, low_digit)) => {
+                let value = high << 4 | low;
+                if PREPROCESSED_ARRAY[value as usize] == 1 && !set.is_some_and(|set| set.contains(value)) {
+                    push_unchanged(&mut normalized, str_to_normalize, unchanged_start, index);
+                    normalized.push(value as char);
+                } else if high_digit.is_ascii_lowercase() || low_digit.is_ascii_lowercase() {
+                    push_unchanged(&mut normalized, str_to_normalize, unchanged_start, index);
+                    normalized.push('%');
+                    normalized.push(HEX_DIGITS[high as usize] as char);
+                    normalized.push(HEX_DIGITS[low as usize] as char);
+                } else {
+                    index += 3;
+                    continue;
+                }
+                3
+            }
+            None => match set {
+                Some(set) if set.contains(byte) => {
+                    push_unchanged(&mut normalized, str_to_normalize, unchanged_start, index);
+                    encode_bytes_into(&mut normalized, &[byte], set, Mode::Rfc3986, HexCase::Upper);
+                    1
+                }
+                _ => {
+                    index += 1;
+                    continue;
+                }
+            },
+        };
+
+        index += replaced_length;
+        unchanged_start = index;
+    }
+
+    if unchanged_start == 0 {
+        return Cow::Borrowed(str_to_normalize);
+    }
+    normalized.push_str(&str_to_normalize[unchanged_start..]);
+
+    Cow::Owned(normalized)
+}
+
+/// Appends the unchanged text between two replacements.
+///
+/// Raw bytes are only replaced when they are part of the set, and every byte of a non-ASCII character
+/// is part of any set. So `start` is only inside a character while the rest of the character gets
+/// replaced, then `start` equals `end`.
+fn push_unchanged(normalized: &mut String, str_to_normalize: &str, start: usize, end: usize) {
+    if start < end {
+        normalized.push_str(&str_to_normalize[start..end]);
+    }
 }
 
 /// Decodes a URL-encoded string.
@@ -170,8 +539,88 @@ pub fn encode(str_to_encode: &str) -> String {
 /// assert_eq!(decoded, "Hello, World!");
 /// ```
 pub fn decode(str_to_decode: &str) -> String {
-    let mut decoded_bytes = Vec::with_capacity(str_to_decode.len());
-    let bytes = str_to_decode.as_bytes();
+    decode_with_mode(str_to_decode, Mode::FormUrlEncoded)
+}
+
+/// Decodes a URL-encoded string using the given [`Mode`].
+///
+/// In [`Mode::Rfc3986`] a `+` is kept as a literal plus sign,
+/// in [`Mode::FormUrlEncoded`] it is decoded as a space just like [`decode`] does.
+///
+/// # Arguments
+///
+/// * `str_to_decode` - A string slice that holds the text to be URL decoded.
+/// * `mode` - Decides whether `+` gets decoded as a space.
+///
+/// # Returns
+///
+/// A new `String` containing the decoded text.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{decode_with_mode, Mode};
+///
+/// assert_eq!(decode_with_mode("/c++/my%20docs", Mode::Rfc3986), "/c++/my docs");
+/// assert_eq!(decode_with_mode("c%2B%2B+docs", Mode::FormUrlEncoded), "c++ docs");
+/// ```
+pub fn decode_with_mode(str_to_decode: &str, mode: Mode) -> String {
+    String::from_utf8_lossy(&decode_to_bytes_with_mode(str_to_decode.as_bytes(), mode)).into_owned()
+}
+
+/// Decodes URL-encoded data into raw bytes.
+///
+/// Works like [`decode`], but the decoded bytes are returned as-is instead of being
+/// converted to UTF-8, so arbitrary octets round-trip exactly.
+///
+/// # Arguments
+///
+/// * `bytes_to_decode` - A byte slice that holds the data to be URL decoded.
+///
+/// # Returns
+///
+/// A new `Vec<u8>` containing the decoded bytes.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{decode_to_bytes, encode_bytes};
+///
+/// let info_hash = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF1];
+/// assert_eq!(decode_to_bytes(encode_bytes(&info_hash).as_bytes()), info_hash);
+/// assert_eq!(decode_to_bytes(b"caf%E9"), b"caf\xE9");
+/// ```
+pub fn decode_to_bytes(bytes_to_decode: &[u8]) -> Vec<u8> {
+    decode_to_bytes_with_mode(bytes_to_decode, Mode::FormUrlEncoded)
+}
+
+/// Decodes URL-encoded data into raw bytes using the given [`Mode`].
+///
+/// See [`decode_to_bytes`] and [`decode_with_mode`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{decode_to_bytes_with_mode, Mode};
+///
+/// assert_eq!(decode_to_bytes_with_mode(b"a+b%FF", Mode::Rfc3986), b"a+b\xFF");
+/// ```
+pub fn decode_to_bytes_with_mode(bytes_to_decode: &[u8], mode: Mode) -> Vec<u8> {
+    let mut decoded_bytes = Vec::with_capacity(bytes_to_decode.len());
+    decode_bytes_into(&mut decoded_bytes, bytes_to_decode, mode, true);
+
+    decoded_bytes
+}
+
+/// Appends the decoded bytes to `decoded_bytes`.
+///
+/// If `is_last_chunk` is `false`, an escape cut off by the end of `bytes` is left undecoded,
+/// so it can be completed by the next chunk.
+///
+/// # Returns
+///
+/// The number of bytes of `bytes` that were consumed.
+pub(crate) fn decode_bytes_into(decoded_bytes: &mut Vec<u8>, bytes: &[u8], mode: Mode, is_last_chunk: bool) -> usize {
     let mut i = 0;
 
     while i < bytes.len() {
@@ -187,7 +636,10 @@ pub fn decode(str_to_decode: &str) -> String {
                 decoded_bytes.push(bytes[i + 2]);
                 i += 3;
             }
-        } else if bytes[i] == b'+' {
+        } else if bytes[i] == b'%' && !is_last_chunk {
+            // The escape continues in the next chunk
+            break;
+        } else if bytes[i] == b'+' && mode.space_as_plus() {
             // Some legacy systems decode a space as '+'
             decoded_bytes.push(b' ');
             i += 1;
@@ -198,7 +650,118 @@ pub fn decode(str_to_decode: &str) -> String {
         }
     }
 
-    String::from_utf8_lossy(&decoded_bytes).into_owned()
+    i
+}
+
+/// Decodes a URL-encoded string, borrowing the input when nothing needs to be decoded.
+///
+/// Produces the same text as [`decode`], but only allocates if the input contains a `%` or a `+`.
+///
+/// # Arguments
+///
+/// * `str_to_decode` - A string slice that holds the text to be URL decoded.
+///
+/// # Returns
+///
+/// A `Cow::Borrowed` of the input if it contains nothing to decode, otherwise a `Cow::Owned` with the decoded text.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use url_encor::decode_cow;
+///
+/// assert!(matches!(decode_cow("user_42"), Cow::Borrowed("user_42")));
+/// assert_eq!(decode_cow("user%2042"), "user 42");
+/// ```
+pub fn decode_cow(str_to_decode: &str) -> Cow<'_, str> {
+    if str_to_decode.bytes().any(|byte| byte == b'%' || byte == b'+') {
+        Cow::Owned(decode(str_to_decode))
+    } else {
+        Cow::Borrowed(str_to_decode)
+    }
+}
+
+/// Strictly decodes a URL-encoded string.
+///
+/// Works like [`decode`], but instead of passing malformed escapes through and
+/// replacing invalid UTF-8, it reports the first problem it finds.
+///
+/// # Arguments
+///
+/// * `str_to_decode` - A string slice that holds the text to be URL decoded.
+///
+/// # Returns
+///
+/// The decoded `String`, or a [`DecodeError`] carrying the byte offset of the problem.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{try_decode, DecodeError};
+///
+/// assert_eq!(try_decode("Hello%2C+World%21").unwrap(), "Hello, World!");
+/// assert_eq!(try_decode("%G1"), Err(DecodeError::InvalidHexDigit { offset: 1, byte: b'G' }));
+/// assert_eq!(try_decode("100%2"), Err(DecodeError::TruncatedEscape { offset: 3 }));
+/// assert_eq!(try_decode("%FF"), Err(DecodeError::InvalidUtf8 { offset: 0 }));
+/// ```
+pub fn try_decode(str_to_decode: &str) -> Result<String, DecodeError> {
+    try_decode_with_mode(str_to_decode, Mode::FormUrlEncoded)
+}
+
+/// Strictly decodes a URL-encoded string using the given [`Mode`].
+///
+/// See [`try_decode`] and [`decode_with_mode`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{try_decode_with_mode, Mode};
+///
+/// assert_eq!(try_decode_with_mode("/c++/docs", Mode::Rfc3986).unwrap(), "/c++/docs");
+/// ```
+pub fn try_decode_with_mode(str_to_decode: &str, mode: Mode) -> Result<String, DecodeError> {
+    let mut decoded_bytes = Vec::with_capacity(str_to_decode.len());
+    let bytes = str_to_decode.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                if i + 2 >= bytes.len() {
+                    return Err(DecodeError::TruncatedEscape { offset: i });
+                }
+                let n1 = from_hex(bytes[i + 1])
+                    .ok_or(DecodeError::InvalidHexDigit { offset: i + 1, byte: bytes[i + 1] })?;
+                let n2 = from_hex(bytes[i + 2])
+                    .ok_or(DecodeError::InvalidHexDigit { offset: i + 2, byte: bytes[i + 2] })?;
+                decoded_bytes.push((n1 << 4) | n2);
+                i += 3;
+            }
+            b'+' if mode.space_as_plus() => {
+                decoded_bytes.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded_bytes.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded_bytes).map_err(|error| DecodeError::InvalidUtf8 {
+        offset: input_offset(bytes, error.utf8_error().valid_up_to()),
+    })
+}
+
+/// Maps an offset into the decoded bytes back to the offset into the (valid) encoded input.
+fn input_offset(encoded: &[u8], decoded_offset: usize) -> usize {
+    let mut input_offset = 0;
+    for _ in 0..decoded_offset {
+        input_offset += if encoded[input_offset] == b'%' { 3 } else { 1 };
+    }
+
+    input_offset
 }
 
 /// Converts a hexadecimal character (represented as byte) to its corresponding decimal value (represented as byte as well).
@@ -221,6 +784,45 @@ fn from_hex(c: u8) -> Option<u8> {
 
         /// Decodes the value from URL encoding.
         fn url_decode(&self) -> T;
+
+        /// Encodes the value using URL encoding in the given [`Mode`].
+        ///
+        /// # Default implementation
+        ///
+        /// **The default implementation ignores `mode`** and returns [`Encoder::url_encode`],
+        /// so a type that doesn't override this method encodes the same way for every [`Mode`].
+        /// Implementors that support modes have to override it,
+        /// the implementations for `String`, `&str` and `Vec<String>` do.
+        fn url_encode_with_mode(&self, mode: Mode) -> T {
+            let _ = mode;
+            self.url_encode()
+        }
+
+        /// Decodes the value from URL encoding in the given [`Mode`].
+        ///
+        /// # Default implementation
+        ///
+        /// **The default implementation ignores `mode`** and returns [`Encoder::url_decode`],
+        /// so a type that doesn't override this method decodes the same way for every [`Mode`].
+        /// Implementors that support modes have to override it,
+        /// the implementations for `String`, `&str` and `Vec<String>` do.
+        fn url_decode_with_mode(&self, mode: Mode) -> T {
+            let _ = mode;
+            self.url_decode()
+        }
+
+        /// Encodes the value using URL encoding, writing the escapes with the given [`HexCase`].
+        ///
+        /// # Default implementation
+        ///
+        /// **The default implementation ignores `case`** and returns [`Encoder::url_encode`],
+        /// so a type that doesn't override this method writes the same escapes for every [`HexCase`],
+        /// even for [`HexCase::Lower`]. Implementors that support both cases have to override it,
+        /// the implementations for `String`, `&str` and `Vec<String>` do.
+        fn url_encode_with_case(&self, case: HexCase) -> T {
+            let _ = case;
+            self.url_encode()
+        }
     }
 
 
@@ -235,7 +837,14 @@ mod tests {
 
     #[test]
     fn test_preprocessed_ascii_convertable() {
-        assert_eq!(PREPROCESSED_ARRAY, preprocessing::preprocess_ascii_convertable())
+        assert_eq!(preprocessing::PREPROCESSED_ARRAY, preprocessing::preprocess_ascii_convertable())
+    }
+
+    #[test]
+    fn test_default_encode_set() {
+        for (byte, &convertable) in preprocessing::PREPROCESSED_ARRAY.iter().enumerate() {
+            assert_eq!(EncodeSet::DEFAULT.contains(byte as u8), convertable == 0, "byte {}", byte)
+        }
     }
 
     #[test]
@@ -251,6 +860,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::needless_range_loop, clippy::assertions_on_constants)]
     fn test_from_hex_function() {
         for i in 0..HEX_BYTE_TO_HEX_VALUE.len() {
             let result1 = from_hex(i as u8);
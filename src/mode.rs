@@ -0,0 +1,26 @@
+/// # How spaces and `+` are treated
+///
+/// URLs and HTML forms disagree on the meaning of `+`:
+///
+/// - In [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986) components like paths, `+` is a literal plus sign
+///   and a space is always written as `%20`.
+/// - In `application/x-www-form-urlencoded` data, `+` stands for a space.
+///
+/// [`encode`](crate::encode) behaves like [`Mode::Rfc3986`],
+/// while [`decode`](crate::decode) behaves like [`Mode::FormUrlEncoded`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Spaces are encoded as `%20` and `+` is decoded as a literal `+`.
+    Rfc3986,
+
+    /// Spaces are encoded as `+` and `+` is decoded as a space.
+    FormUrlEncoded,
+}
+
+impl Mode {
+    /// Returns `true` if a space is written as `+` in this mode.
+    #[inline]
+    pub(crate) fn space_as_plus(self) -> bool {
+        self == Mode::FormUrlEncoded
+    }
+}
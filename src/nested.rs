@@ -0,0 +1,342 @@
+//! # Nested query parameters
+//!
+//! Parsing and serializing of the bracket syntax used by Rails, PHP and many JavaScript libraries,
+//! like `filter[status]=open&ids[]=1&ids[]=2`.
+//!
+//! Names are split into a root and bracket segments:
+//!
+//! - `a[b]` stores a value under the key `b` of the map `a`.
+//! - `a[]` appends a value to the array `a`.
+//! - `a[0]` stores a value at index `0` of the array `a`. Arrays are compacted,
+//!   so `a[3]=x&a[7]=y` results in an array with two elements.
+//!
+//! Names that don't follow this syntax, like `a[b` or `[a]`, are used as plain keys.
+//! Repeating a plain name keeps the last value.
+//!
+//! The nesting depth and the array indices are limited by [`NestedOptions`],
+//! so untrusted input can't make the parser build huge trees.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::form::{self, Serializer};
+use crate::NestedError;
+
+/// # A value of a nested query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    /// A plain, decoded value.
+    String(String),
+
+    /// An array, built from `a[]` or `a[0]`.
+    Array(Vec<Value>),
+
+    /// A map, built from `a[key]`. Keeps the order in which the keys appeared first.
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Returns the string if this is a [`Value::String`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if this is a [`Value::Array`].
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(elements) => Some(elements),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if this is a [`Value::Map`].
+    pub fn as_map(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns the value stored under `key` if this is a [`Value::Map`].
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        get(self.as_map()?, key)
+    }
+}
+
+/// Returns the value stored under `key` in a parsed query.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::nested;
+///
+/// let query = nested::parse("filter[status]=open").unwrap();
+/// let status = nested::get(&query, "filter").and_then(|filter| filter.get("status"));
+/// assert_eq!(status.and_then(|status| status.as_str()), Some("open"));
+/// ```
+pub fn get<'a>(entries: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    entries.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+}
+
+/// # Limits for the nested query parser
+///
+/// ```
+/// use url_encor::nested::NestedOptions;
+///
+/// const STRICT: NestedOptions = NestedOptions::new().max_depth(2).max_array_index(10);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NestedOptions {
+    max_depth: usize,
+    max_array_index: usize,
+}
+
+impl NestedOptions {
+    /// Creates the default limits: a depth of `5` and an array index of `100`.
+    pub const fn new() -> Self {
+        NestedOptions {
+            max_depth: 5,
+            max_array_index: 100,
+        }
+    }
+
+    /// Sets the maximum number of bracket segments after the root, `a[b][c]` has a depth of `2`.
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the largest array index, explicit or created by `[]`, that gets accepted.
+    pub const fn max_array_index(mut self, max_array_index: usize) -> Self {
+        self.max_array_index = max_array_index;
+        self
+    }
+}
+
+impl Default for NestedOptions {
+    fn default() -> Self {
+        NestedOptions::new()
+    }
+}
+
+/// Parses a nested query using the default [`NestedOptions`].
+///
+/// # Arguments
+///
+/// * `input` - The form data, without a leading `?`.
+///
+/// # Returns
+///
+/// The top-level entries, or a [`NestedError`] if a limit is exceeded or two parameters conflict.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::nested::{self, Value};
+///
+/// let query = nested::parse("filter[status]=open&ids[]=1&ids[]=2").unwrap();
+/// assert_eq!(query, [
+///     ("filter".to_string(), Value::Map(vec![("status".to_string(), Value::String("open".to_string()))])),
+///     ("ids".to_string(), Value::Array(vec![Value::String("1".to_string()), Value::String("2".to_string())])),
+/// ]);
+/// ```
+pub fn parse(input: &str) -> Result<Vec<(String, Value)>, NestedError> {
+    parse_with(input, &NestedOptions::new())
+}
+
+/// Parses a nested query using custom [`NestedOptions`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{nested::{self, NestedOptions}, NestedError};
+///
+/// let options = NestedOptions::new().max_depth(1);
+/// assert!(nested::parse_with("a[b]=1", &options).is_ok());
+/// assert_eq!(nested::parse_with("a[b][c]=1", &options),
+///            Err(NestedError::DepthLimitExceeded { name: "a[b][c]".to_string() }));
+/// ```
+pub fn parse_with(input: &str, options: &NestedOptions) -> Result<Vec<(String, Value)>, NestedError> {
+    let mut root = Node::Map(MapNode::default());
+
+    for (name, value) in form::parse(input) {
+        let segments = split_name(&name, options)?;
+        assign(&mut root, &segments, value.into_owned(), &name, options)?;
+    }
+
+    match root.into_value() {
+        Value::Map(entries) => Ok(entries),
+        _ => unreachable!("the root is always a map"),
+    }
+}
+
+/// Serializes a nested query into `application/x-www-form-urlencoded` data.
+///
+/// Arrays of plain values are written as `a[]=1&a[]=2`, arrays that contain maps or arrays
+/// are written with explicit indices like `a[0][b]=1`, so the result parses back into the same tree.
+/// Empty arrays and maps are left out.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::nested;
+///
+/// let query = nested::parse("filter[status]=open&ids[]=1&ids[]=2").unwrap();
+/// assert_eq!(nested::serialize(&query), "filter%5Bstatus%5D=open&ids%5B%5D=1&ids%5B%5D=2");
+/// ```
+pub fn serialize(entries: &[(String, Value)]) -> String {
+    let mut serializer = Serializer::new(String::new());
+    for (name, value) in entries {
+        serialize_value(&mut serializer, name, value);
+    }
+
+    serializer.finish()
+}
+
+fn serialize_value(serializer: &mut Serializer<String>, name: &str, value: &Value) {
+    match value {
+        // Writing into a `String` never fails
+        Value::String(value) => {
+            let _ = serializer.append_pair(name, value);
+        }
+        Value::Array(elements) => {
+            for (index, element) in elements.iter().enumerate() {
+                match element {
+                    Value::String(_) => serialize_value(serializer, &format!("{}[]", name), element),
+                    _ => serialize_value(serializer, &format!("{}[{}]", name, index), element),
+                }
+            }
+        }
+        Value::Map(entries) => {
+            for (key, value) in entries {
+                serialize_value(serializer, &format!("{}[{}]", name, key), value);
+            }
+        }
+    }
+}
+
+/// The tree while it gets built.
+enum Node {
+    /// A slot that was just created and has not been assigned yet
+    Empty,
+    String(String),
+    /// Sparse while parsing, compacted into a `Vec` in the end
+    Array(BTreeMap<usize, Node>),
+    Map(MapNode),
+}
+
+#[derive(Default)]
+struct MapNode {
+    entries: Vec<(String, Node)>,
+    positions: HashMap<String, usize>,
+}
+
+impl MapNode {
+    fn entry(&mut self, key: &str) -> &mut Node {
+        let position = match self.positions.get(key) {
+            Some(&position) => position,
+            None => {
+                self.positions.insert(key.to_string(), self.entries.len());
+                self.entries.push((key.to_string(), Node::Empty));
+                self.entries.len() - 1
+            }
+        };
+
+        &mut self.entries[position].1
+    }
+}
+
+impl Node {
+    fn into_value(self) -> Value {
+        match self {
+            Node::Empty => Value::String(String::new()),
+            Node::String(value) => Value::String(value),
+            Node::Array(elements) => Value::Array(elements.into_values().map(Node::into_value).collect()),
+            Node::Map(map) => Value::Map(
+                map.entries
+                    .into_iter()
+                    .map(|(key, node)| (key, node.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Splits a name like `a[b][]` into its segments `["a", "b", ""]`.
+///
+/// Names that don't follow the bracket syntax are returned as a single segment.
+fn split_name<'n>(name: &'n str, options: &NestedOptions) -> Result<Vec<&'n str>, NestedError> {
+    let open = match name.find('[') {
+        Some(open) if open > 0 => open,
+        _ => return Ok(vec![name]),
+    };
+
+    let (root, mut rest) = name.split_at(open);
+    let mut segments = vec![root];
+    while !rest.is_empty() {
+        match rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            Some((segment, remaining)) => {
+                if segments.len() > options.max_depth {
+                    return Err(NestedError::DepthLimitExceeded { name: name.to_string() });
+                }
+                segments.push(segment);
+                rest = remaining;
+            }
+            None => return Ok(vec![name]),
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Returns `true` if a segment is an array index like `0` or `12`.
+fn is_index(segment: &str) -> bool {
+    !segment.is_empty() && segment.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Stores `value` at the path described by `segments`, creating maps and arrays on the way.
+fn assign(node: &mut Node, segments: &[&str], value: String, name: &str, options: &NestedOptions) -> Result<(), NestedError> {
+    let conflict = || NestedError::TypeConflict { name: name.to_string() };
+
+    let (&segment, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => {
+            return match node {
+                Node::Empty | Node::String(_) => {
+                    *node = Node::String(value);
+                    Ok(())
+                }
+                _ => Err(conflict()),
+            };
+        }
+    };
+
+    if let Node::Empty = node {
+        *node = if segment.is_empty() || is_index(segment) {
+            Node::Array(BTreeMap::new())
+        } else {
+            Node::Map(MapNode::default())
+        };
+    }
+
+    match node {
+        Node::Array(elements) => {
+            let index = if segment.is_empty() {
+                elements.keys().next_back().map_or(0, |last| last + 1)
+            } else if is_index(segment) {
+                segment.parse().unwrap_or(usize::MAX)
+            } else {
+                return Err(conflict());
+            };
+            if index > options.max_array_index {
+                return Err(NestedError::ArrayIndexLimitExceeded { name: name.to_string() });
+            }
+            assign(elements.entry(index).or_insert(Node::Empty), rest, value, name, options)
+        }
+        // `a[]` can't append to a map, but numeric keys like `a[0]` are fine
+        Node::Map(map) if !segment.is_empty() => assign(map.entry(segment), rest, value, name, options),
+        _ => Err(conflict()),
+    }
+}
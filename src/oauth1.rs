@@ -0,0 +1,141 @@
+//! # OAuth 1.0a signature base strings
+//!
+//! Builds the [signature base string](https://www.rfc-editor.org/rfc/rfc5849#section-3.4.1) of
+//! RFC 5849, the text that gets signed with HMAC-SHA1, RSA-SHA1 or another method.
+//! Signing itself is left to the caller.
+//!
+//! RFC 5849 encodes everything except the RFC 3986 unreserved characters with uppercase hex digits,
+//! which is exactly what [`encode`](crate::encode) does.
+
+use std::borrow::Borrow;
+
+use crate::{encode, form, Url, UrlError};
+
+/// Returns the signature base string of a request.
+///
+/// The parameters of the query of `url` are included automatically. `parameters` has to contain
+/// the `oauth_*` protocol parameters and, for `application/x-www-form-urlencoded` requests, the
+/// parameters of the body. All parameters are passed decoded. An `oauth_signature` parameter is ignored.
+///
+/// # Arguments
+///
+/// * `method` - The HTTP method, it gets uppercased.
+/// * `url` - The absolute URL of the request, including the query.
+/// * `parameters` - The decoded `(name, value)` pairs of the protocol parameters and the body.
+///
+/// # Returns
+///
+/// The signature base string, or a [`UrlError`] if `url` can't be parsed.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::oauth1;
+///
+/// let base_string = oauth1::signature_base_string("get", "https://Api.Example.com:443/items?q=a+b", [
+///     ("oauth_consumer_key", "key"),
+///     ("oauth_nonce", "n"),
+/// ]).unwrap();
+/// assert_eq!(base_string, "GET&https%3A%2F%2Fapi.example.com%2Fitems&oauth_consumer_key%3Dkey%26oauth_nonce%3Dn%26q%3Da%2520b");
+/// ```
+pub fn signature_base_string<I, K, V>(method: &str, url: &str, parameters: I) -> Result<String, UrlError>
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let url = Url::parse(url)?;
+
+    let mut all_parameters: Vec<(String, String)> = form::parse(url.query().unwrap_or(""))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    for parameter in parameters {
+        let (name, value) = parameter.borrow();
+        all_parameters.push((name.as_ref().to_string(), value.as_ref().to_string()));
+    }
+    all_parameters.retain(|(name, _)| name != "oauth_signature");
+
+    Ok(format!(
+        "{}&{}&{}",
+        encode(&method.to_ascii_uppercase()),
+        encode(&base_string_uri(&url)),
+        encode(&normalize_parameters(all_parameters))
+    ))
+}
+
+/// Returns the [base string URI](https://www.rfc-editor.org/rfc/rfc5849#section-3.4.1.2) of a URL.
+///
+/// The scheme and the host are lowercase, the default port of `http` and `https` is left out,
+/// the query and the fragment are removed.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{oauth1, Url};
+///
+/// let url = Url::parse("HTTP://EXAMPLE.COM:80/r%20v/X?id=123").unwrap();
+/// assert_eq!(oauth1::base_string_uri(&url), "http://example.com/r%20v/X");
+///
+/// let url = Url::parse("https://www.example.net:8080/?q=1").unwrap();
+/// assert_eq!(oauth1::base_string_uri(&url), "https://www.example.net:8080/");
+/// ```
+pub fn base_string_uri(url: &Url) -> String {
+    let mut uri = format!("{}://{}", url.scheme(), url.host_str().unwrap_or(""));
+    if let Some(port) = url.port() {
+        uri.push(':');
+        uri.push_str(&port.to_string());
+    }
+    uri.push_str(&url.path());
+
+    uri
+}
+
+/// Returns the [normalized parameters](https://www.rfc-editor.org/rfc/rfc5849#section-3.4.1.3.2).
+///
+/// Names and values are encoded, the pairs are sorted by encoded name, then by encoded value,
+/// and joined with `&`.
+///
+/// # Arguments
+///
+/// * `parameters` - The decoded `(name, value)` pairs.
+///
+/// # Returns
+///
+/// The normalized parameters as a new `String`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::oauth1;
+///
+/// assert_eq!(oauth1::normalize_parameters([("b", "x y"), ("a", "2"), ("a", "1")]), "a=1&a=2&b=x%20y");
+/// ```
+pub fn normalize_parameters<I, K, V>(parameters: I) -> String
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut encoded: Vec<(String, String)> = parameters
+        .into_iter()
+        .map(|parameter| {
+            let (name, value) = parameter.borrow();
+            (encode(name.as_ref()), encode(value.as_ref()))
+        })
+        .collect();
+    encoded.sort_unstable();
+
+    let mut normalized = String::new();
+    for (name, value) in encoded {
+        if !normalized.is_empty() {
+            normalized.push('&');
+        }
+        normalized.push_str(&name);
+        normalized.push('=');
+        normalized.push_str(&value);
+    }
+
+    normalized
+}
@@ -48,6 +48,8 @@ pub const PREPROCESSED_ARRAY: [u8; 256] =
 
 pub const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
 
+pub const HEX_DIGITS_LOWER: &[u8; 16] = b"0123456789abcdef";
+
 /// # Preprocessed Hexadecimal in bytes and their values
 /// This is the result of calling the `printable_array(Vec::from(from_hex_bytes_to_value_bytes()), 6);`
 ///
@@ -98,6 +100,7 @@ pub const HEX_BYTE_TO_HEX_VALUE: [i16; 256] =
 /// This function converts bytes that represent hex values in ascii to their
 /// respective values
 #[allow(dead_code)]
+#[allow(clippy::needless_range_loop)]
 pub fn from_hex_bytes_to_value_bytes() -> [i16; 256] {
     let mut array = [-1; 256];
 
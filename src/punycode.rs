@@ -0,0 +1,221 @@
+//! # Punycode
+//!
+//! The [Bootstring](https://www.rfc-editor.org/rfc/rfc3492) encoding that represents Unicode labels of
+//! internationalized domain names with the ASCII characters allowed in hostnames.
+//!
+//! These functions work on single labels without the `xn--` prefix.
+//!
+//! # Examples
+//!
+//! ```
+//! use url_encor::punycode;
+//!
+//! assert_eq!(punycode::encode("bücher").unwrap(), "bcher-kva");
+//! assert_eq!(punycode::decode("bcher-kva").unwrap(), "bücher");
+//! ```
+
+use crate::PunycodeError;
+
+const BASE: u32 = 36;
+const T_MIN: u32 = 1;
+const T_MAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 0x80;
+const DELIMITER: char = '-';
+
+/// The longest input [`decode`] accepts, in bytes.
+///
+/// Decoding inserts every code point into the middle of the output, which takes quadratic time,
+/// so longer input is rejected up front. A DNS label has at most 63 bytes.
+pub const MAX_DECODE_LENGTH: usize = 4096;
+
+/// Adapts the bias after each delta, as described in RFC 3492, section 6.1.
+fn adapt(mut delta: u32, num_points: u32, first_time: bool) -> u32 {
+    delta /= if first_time { DAMP } else { 2 };
+    delta += delta / num_points;
+
+    let mut k = 0;
+    while delta > ((BASE - T_MIN) * T_MAX) / 2 {
+        delta /= BASE - T_MIN;
+        k += BASE;
+    }
+
+    k + (BASE - T_MIN + 1) * delta / (delta + SKEW)
+}
+
+/// The threshold for the digit at position `k`.
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        T_MIN
+    } else if k >= bias + T_MAX {
+        T_MAX
+    } else {
+        k - bias
+    }
+}
+
+/// Maps `0..=25` to `a..=z` and `26..=35` to `0..=9`.
+fn encode_digit(digit: u32) -> char {
+    match digit {
+        0..=25 => (b'a' + digit as u8) as char,
+        _ => (b'0' + (digit - 26) as u8) as char,
+    }
+}
+
+fn decode_digit(byte: u8) -> Option<u32> {
+    match byte {
+        b'a'..=b'z' => Some((byte - b'a') as u32),
+        b'A'..=b'Z' => Some((byte - b'A') as u32),
+        b'0'..=b'9' => Some((byte - b'0') as u32 + 26),
+        _ => None,
+    }
+}
+
+/// Encodes a Unicode label to Punycode.
+///
+/// The basic code points are copied in order, followed by `-` if there are any,
+/// then the non-ASCII code points are encoded as lowercase digits.
+///
+/// # Arguments
+///
+/// * `input` - The label to encode.
+///
+/// # Returns
+///
+/// The encoded label without the `xn--` prefix, or [`PunycodeError::Overflow`] if the label is too long.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::punycode;
+///
+/// assert_eq!(punycode::encode("münchen").unwrap(), "mnchen-3ya");
+/// assert_eq!(punycode::encode("example").unwrap(), "example-");
+/// ```
+pub fn encode(input: &str) -> Result<String, PunycodeError> {
+    let code_points: Vec<u32> = input.chars().map(u32::from).collect();
+    let mut output: String = input.chars().filter(char::is_ascii).collect();
+
+    let basic_count = output.len() as u32;
+    if basic_count > 0 {
+        output.push(DELIMITER);
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut handled = basic_count;
+
+    while (handled as usize) < code_points.len() {
+        // The smallest code point that is not handled yet
+        let m = code_points.iter().copied().filter(|&c| c >= n).min().unwrap_or(n);
+        let increase = (m - n).checked_mul(handled + 1).ok_or(PunycodeError::Overflow)?;
+        delta = delta.checked_add(increase).ok_or(PunycodeError::Overflow)?;
+        n = m;
+
+        for &c in &code_points {
+            if c < n {
+                delta = delta.checked_add(1).ok_or(PunycodeError::Overflow)?;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+
+                bias = adapt(delta, handled + 1, handled == basic_count);
+                delta = 0;
+                handled += 1;
+            }
+        }
+
+        delta = delta.checked_add(1).ok_or(PunycodeError::Overflow)?;
+        n = n.checked_add(1).ok_or(PunycodeError::Overflow)?;
+    }
+
+    Ok(output)
+}
+
+/// Decodes a Punycode label to Unicode.
+///
+/// Everything before the last `-` is copied as-is, digits are accepted in both cases.
+///
+/// # Arguments
+///
+/// * `input` - The encoded label without the `xn--` prefix.
+///
+/// # Returns
+///
+/// The decoded label, or a [`PunycodeError`] if the input is not valid Punycode
+/// or longer than [`MAX_DECODE_LENGTH`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::{punycode, PunycodeError};
+///
+/// assert_eq!(punycode::decode("mnchen-3ya").unwrap(), "münchen");
+/// assert_eq!(punycode::decode("mnchen-3y!"), Err(PunycodeError::InvalidCharacter { offset: 9 }));
+/// ```
+pub fn decode(input: &str) -> Result<String, PunycodeError> {
+    if input.len() > MAX_DECODE_LENGTH {
+        return Err(PunycodeError::TooLong);
+    }
+
+    let bytes = input.as_bytes();
+    if let Some(offset) = bytes.iter().position(|byte| !byte.is_ascii()) {
+        return Err(PunycodeError::InvalidCharacter { offset });
+    }
+
+    let (mut output, digits_start): (Vec<char>, usize) = match input.rfind(DELIMITER) {
+        Some(delimiter) => (input[..delimiter].chars().collect(), delimiter + 1),
+        None => (Vec::new(), 0),
+    };
+
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut position = digits_start;
+
+    while position < bytes.len() {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let byte = *bytes.get(position).ok_or(PunycodeError::UnexpectedEnd)?;
+            let digit = decode_digit(byte).ok_or(PunycodeError::InvalidCharacter { offset: position })?;
+            position += 1;
+
+            let step = digit.checked_mul(w).ok_or(PunycodeError::Overflow)?;
+            i = i.checked_add(step).ok_or(PunycodeError::Overflow)?;
+
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t).ok_or(PunycodeError::Overflow)?;
+            k += BASE;
+        }
+
+        let length = output.len() as u32 + 1;
+        bias = adapt(i - old_i, length, old_i == 0);
+        n = n.checked_add(i / length).ok_or(PunycodeError::Overflow)?;
+        i %= length;
+
+        let c = char::from_u32(n).ok_or(PunycodeError::InvalidCodePoint)?;
+        output.insert(i as usize, c);
+        i += 1;
+    }
+
+    Ok(output.into_iter().collect())
+}
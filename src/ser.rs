@@ -0,0 +1,616 @@
+use std::fmt::Display;
+
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::form::Serializer as FormSerializer;
+use crate::SerdeError;
+
+/// Serializes a value into `application/x-www-form-urlencoded` data.
+///
+/// The top-level value has to be a struct, a map or a sequence of `(name, value)` pairs.
+/// Values can be strings, numbers, booleans, chars, unit enum variants and options.
+/// `None` values are skipped, sequences repeat their name for every element.
+///
+/// Names and values are encoded like [`form::Serializer`](crate::form::Serializer) does.
+///
+/// # Arguments
+///
+/// * `value` - The value to serialize.
+///
+/// # Returns
+///
+/// A new `String` containing the encoded pairs, or a [`SerdeError`] if the value can't be represented.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Search {
+///     query: String,
+///     page: u32,
+///     tags: Vec<&'static str>,
+///     lang: Option<&'static str>,
+/// }
+///
+/// let search = Search { query: "rust lang".into(), page: 2, tags: vec!["a", "b&c"], lang: None };
+/// assert_eq!(url_encor::to_string(&search).unwrap(), "query=rust+lang&page=2&tags=a&tags=b%26c");
+/// ```
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, SerdeError> {
+    let mut output = FormSerializer::new(String::new());
+    value.serialize(TopSerializer { output: &mut output })?;
+
+    Ok(output.finish())
+}
+
+fn append_pair(output: &mut FormSerializer<String>, name: &str, value: &str) -> Result<(), SerdeError> {
+    output.append_pair(name, value).map_err(SerdeError::new)
+}
+
+fn unsupported(what: &str, position: &str) -> SerdeError {
+    SerdeError::new(format!("{} is not supported as {}", what, position))
+}
+
+/// Serializes the top-level value, which has to be a struct, a map or a sequence of pairs.
+struct TopSerializer<'o> {
+    output: &'o mut FormSerializer<String>,
+}
+
+macro_rules! top_level_unsupported {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(fn $method(self, _: $type) -> Result<(), SerdeError> {
+            Err(unsupported(stringify!($type), "top-level value"))
+        })*
+    };
+}
+
+impl<'o> ser::Serializer for TopSerializer<'o> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = PairsSerializer<'o>;
+    type SerializeTuple = PairsSerializer<'o>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = MapSerializer<'o>;
+    type SerializeStruct = StructSerializer<'o>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    top_level_unsupported!(
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str), serialize_bytes(&[u8]),
+    );
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<(), SerdeError> {
+        Err(unsupported("enum", "top-level value"))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), SerdeError> {
+        Err(unsupported("enum", "top-level value"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Ok(PairsSerializer { output: self.output })
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Ok(PairsSerializer { output: self.output })
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(unsupported("tuple struct", "top-level value"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(unsupported("enum", "top-level value"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Ok(MapSerializer { output: self.output, name: None })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Ok(StructSerializer { output: self.output })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(unsupported("enum", "top-level value"))
+    }
+}
+
+/// Serializes the fields of a top-level struct as pairs.
+struct StructSerializer<'o> {
+    output: &'o mut FormSerializer<String>,
+}
+
+impl ser::SerializeStruct for StructSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(ValueSerializer { name: key, output: self.output })
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+/// Serializes the entries of a top-level map as pairs.
+struct MapSerializer<'o> {
+    output: &'o mut FormSerializer<String>,
+    name: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.name = Some(key.serialize(PartSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let name = self.name.take().ok_or_else(|| SerdeError::new("map value serialized before its key"))?;
+        value.serialize(ValueSerializer { name: &name, output: self.output })
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+/// Serializes the elements of a top-level sequence, each has to be a `(name, value)` pair.
+struct PairsSerializer<'o> {
+    output: &'o mut FormSerializer<String>,
+}
+
+impl ser::SerializeSeq for PairsSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(PairSerializer { output: self.output })
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for PairsSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+/// Serializes a single `(name, value)` pair of a top-level sequence.
+struct PairSerializer<'o> {
+    output: &'o mut FormSerializer<String>,
+}
+
+macro_rules! pair_unsupported {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(fn $method(self, _: $type) -> Result<(), SerdeError> {
+            Err(unsupported(stringify!($type), "sequence element, expected a pair"))
+        })*
+    };
+}
+
+impl<'o> ser::Serializer for PairSerializer<'o> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<(), SerdeError>;
+    type SerializeTuple = PairElementsSerializer<'o>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = Impossible<(), SerdeError>;
+    type SerializeStruct = Impossible<(), SerdeError>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    pair_unsupported!(
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str), serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        Err(unsupported("()", "sequence element, expected a pair"))
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<(), SerdeError> {
+        Err(unsupported("enum", "sequence element, expected a pair"))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), SerdeError> {
+        Err(unsupported("enum", "sequence element, expected a pair"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(unsupported("sequence", "sequence element, expected a pair"))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        if len != 2 {
+            return Err(SerdeError::new(format!("tuple of length {} is not a pair", len)));
+        }
+        Ok(PairElementsSerializer { output: self.output, name: None })
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(unsupported("tuple struct", "sequence element, expected a pair"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(unsupported("enum", "sequence element, expected a pair"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(unsupported("map", "sequence element, expected a pair"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(unsupported("struct", "sequence element, expected a pair"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(unsupported("enum", "sequence element, expected a pair"))
+    }
+}
+
+/// Serializes the name and the value of a pair.
+struct PairElementsSerializer<'o> {
+    output: &'o mut FormSerializer<String>,
+    name: Option<String>,
+}
+
+impl ser::SerializeTuple for PairElementsSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        match self.name.take() {
+            None => self.name = Some(value.serialize(PartSerializer)?),
+            Some(name) => value.serialize(ValueSerializer { name: &name, output: self.output })?,
+        }
+
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+/// Serializes the value of a pair, appending it together with its name.
+struct ValueSerializer<'n, 'o> {
+    name: &'n str,
+    output: &'o mut FormSerializer<String>,
+}
+
+impl ValueSerializer<'_, '_> {
+    fn append(self, value: impl Display) -> Result<(), SerdeError> {
+        append_pair(self.output, self.name, &value.to_string())
+    }
+}
+
+macro_rules! append_displayed {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(fn $method(self, value: $type) -> Result<(), SerdeError> {
+            self.append(value)
+        })*
+    };
+}
+
+impl<'n, 'o> ser::Serializer for ValueSerializer<'n, 'o> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = RepeatedValueSerializer<'n, 'o>;
+    type SerializeTuple = RepeatedValueSerializer<'n, 'o>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = Impossible<(), SerdeError>;
+    type SerializeStruct = Impossible<(), SerdeError>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    append_displayed!(
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str),
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), SerdeError> {
+        Err(unsupported("bytes", "value"))
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        self.append("")
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), SerdeError> {
+        self.append("")
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<(), SerdeError> {
+        self.append(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), SerdeError> {
+        Err(unsupported("enum with data", "value"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Ok(RepeatedValueSerializer { name: self.name, output: self.output })
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Ok(RepeatedValueSerializer { name: self.name, output: self.output })
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(unsupported("tuple struct", "value"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(unsupported("enum with data", "value"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(unsupported("nested map", "value"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(unsupported("nested struct", "value"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(unsupported("enum with data", "value"))
+    }
+}
+
+/// Serializes every element of a sequence value as its own pair with the same name.
+struct RepeatedValueSerializer<'n, 'o> {
+    name: &'n str,
+    output: &'o mut FormSerializer<String>,
+}
+
+impl ser::SerializeSeq for RepeatedValueSerializer<'_, '_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(ValueSerializer { name: self.name, output: self.output })
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for RepeatedValueSerializer<'_, '_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+/// Serializes a name into a `String`.
+struct PartSerializer;
+
+macro_rules! part_to_string {
+    ($($method:ident($type:ty)),* $(,)?) => {
+        $(fn $method(self, value: $type) -> Result<String, SerdeError> {
+            Ok(value.to_string())
+        })*
+    };
+}
+
+impl ser::Serializer for PartSerializer {
+    type Ok = String;
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<String, SerdeError>;
+    type SerializeTuple = Impossible<String, SerdeError>;
+    type SerializeTupleStruct = Impossible<String, SerdeError>;
+    type SerializeTupleVariant = Impossible<String, SerdeError>;
+    type SerializeMap = Impossible<String, SerdeError>;
+    type SerializeStruct = Impossible<String, SerdeError>;
+    type SerializeStructVariant = Impossible<String, SerdeError>;
+
+    part_to_string!(
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str),
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<String, SerdeError> {
+        Err(unsupported("bytes", "name"))
+    }
+
+    fn serialize_none(self) -> Result<String, SerdeError> {
+        Err(unsupported("None", "name"))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerdeError> {
+        Err(unsupported("()", "name"))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<String, SerdeError> {
+        Err(unsupported("unit struct", "name"))
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<String, SerdeError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<String, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, SerdeError> {
+        Err(unsupported("enum with data", "name"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(unsupported("sequence", "name"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(unsupported("tuple", "name"))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(unsupported("tuple struct", "name"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(unsupported("enum with data", "name"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(unsupported("map", "name"))
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(unsupported("struct", "name"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(unsupported("enum with data", "name"))
+    }
+}
@@ -0,0 +1,186 @@
+//! # AWS Signature Version 4 canonicalization
+//!
+//! Builds the canonical URI and the canonical query string of a
+//! [SigV4 canonical request](https://docs.aws.amazon.com/IAM/latest/UserGuide/create-signed-request.html).
+//! Hashing and signing are left to the caller.
+//!
+//! SigV4 encodes everything except the RFC 3986 unreserved characters, which is exactly what
+//! [`encode`](crate::encode) does, always with uppercase hex digits and spaces written as `%20`.
+//!
+//! The inputs are taken as they are sent on the wire, that is already percent-encoded:
+//!
+//! - [`canonical_uri`] is meant for every service except S3. The path gets normalized and each
+//!   segment gets encoded once more, so the original text ends up encoded twice.
+//! - [`canonical_uri_s3`] keeps the path as-is and only makes sure it is encoded exactly once.
+//!
+//! # Examples
+//!
+//! ```
+//! use url_encor::sigv4;
+//!
+//! assert_eq!(sigv4::canonical_uri("/documents and settings/"), "/documents%20and%20settings/");
+//! assert_eq!(sigv4::canonical_uri("/a%20b/../c%20d"), "/c%2520d");
+//! assert_eq!(sigv4::canonical_uri_s3("/my-bucket/a%20b/../c+d"), "/my-bucket/a%20b/../c%2Bd");
+//! assert_eq!(sigv4::canonical_query_string("Param2=value2&Param1=value+1"), "Param1=value%2B1&Param2=value2");
+//! ```
+
+use std::borrow::Borrow;
+
+use crate::{decode_to_bytes_with_mode, encode_bytes, encode_with, normalize_percent_encoding_with, EncodeSet, Mode};
+
+/// Everything except the unreserved characters and `/`.
+const S3_PATH: EncodeSet = EncodeSet::DEFAULT.remove(b'/');
+
+/// Returns the canonical URI for every service except S3.
+///
+/// Dot segments and empty segments are removed, then every segment gets encoded,
+/// including the `%` of existing escapes. A trailing `/` is kept and an empty path becomes `/`.
+///
+/// # Arguments
+///
+/// * `path` - The percent-encoded path of the request, without the query.
+///
+/// # Returns
+///
+/// The canonical URI as a new `String`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::sigv4;
+///
+/// assert_eq!(sigv4::canonical_uri(""), "/");
+/// assert_eq!(sigv4::canonical_uri("//example//"), "/example/");
+/// assert_eq!(sigv4::canonical_uri("/example1/example2/../.."), "/");
+/// ```
+pub fn canonical_uri(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let mut canonical = String::with_capacity(path.len() + 1);
+    for segment in &segments {
+        canonical.push('/');
+        canonical.push_str(&encode_with(segment, &EncodeSet::DEFAULT));
+    }
+
+    let ends_in_directory = path.ends_with('/') || path.ends_with("/.") || path.ends_with("/..");
+    if canonical.is_empty() || ends_in_directory {
+        canonical.push('/');
+    }
+
+    canonical
+}
+
+/// Returns the canonical URI for S3.
+///
+/// S3 signs the object key exactly as it was given, so the path is not normalized.
+/// It is only brought into the one encoding SigV4 accepts: escapes get uppercase hex digits,
+/// escapes of unreserved characters are decoded and every other raw character gets encoded.
+/// An empty path becomes `/`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::sigv4;
+///
+/// assert_eq!(sigv4::canonical_uri_s3("/bucket/photos/été 2024.jpg"), "/bucket/photos/%C3%A9t%C3%A9%202024.jpg");
+/// assert_eq!(sigv4::canonical_uri_s3("/bucket//a/%7e%2f"), "/bucket//a/~%2F");
+/// ```
+pub fn canonical_uri_s3(path: &str) -> String {
+    if path.is_empty() {
+        return String::from("/");
+    }
+
+    normalize_percent_encoding_with(path, &S3_PATH).into_owned()
+}
+
+/// Returns the canonical query string of a percent-encoded query.
+///
+/// Every parameter is decoded and encoded again, so `+` becomes `%2B` and a space is always `%20`.
+/// A parameter without `=` gets an empty value. The parameters are sorted by encoded name,
+/// then by encoded value.
+///
+/// # Arguments
+///
+/// * `query` - The percent-encoded query of the request, without the leading `?`.
+///
+/// # Returns
+///
+/// The canonical query string as a new `String`, empty if there are no parameters.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::sigv4;
+///
+/// assert_eq!(sigv4::canonical_query_string("b=2&a&a=1%2f"), "a=&a=1%2F&b=2");
+/// ```
+pub fn canonical_query_string(query: &str) -> String {
+    let parameters = query
+        .split('&')
+        .filter(|parameter| !parameter.is_empty())
+        .map(|parameter| {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            (reencode(name), reencode(value))
+        })
+        .collect();
+
+    join_sorted(parameters)
+}
+
+/// Returns the canonical query string of parameters that are not encoded yet.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::sigv4;
+///
+/// let query = sigv4::canonical_query_string_from_pairs([("prefix", "photos/2024"), ("list-type", "2")]);
+/// assert_eq!(query, "list-type=2&prefix=photos%2F2024");
+/// ```
+pub fn canonical_query_string_from_pairs<I, K, V>(pairs: I) -> String
+where
+    I: IntoIterator,
+    I::Item: Borrow<(K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let parameters = pairs
+        .into_iter()
+        .map(|pair| {
+            let (name, value) = pair.borrow();
+            (encode_with(name.as_ref(), &EncodeSet::DEFAULT), encode_with(value.as_ref(), &EncodeSet::DEFAULT))
+        })
+        .collect();
+
+    join_sorted(parameters)
+}
+
+/// Decodes a query component and encodes it the way SigV4 expects, invalid UTF-8 is kept byte by byte.
+fn reencode(component: &str) -> String {
+    encode_bytes(&decode_to_bytes_with_mode(component.as_bytes(), Mode::Rfc3986))
+}
+
+fn join_sorted(mut parameters: Vec<(String, String)>) -> String {
+    parameters.sort_unstable();
+
+    let mut query = String::new();
+    for (name, value) in parameters {
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.push_str(&name);
+        query.push('=');
+        query.push_str(&value);
+    }
+
+    query
+}
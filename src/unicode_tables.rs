@@ -0,0 +1,127 @@
+//! # Unicode lookup tables
+//!
+//! The tables are generated by `build.rs` from the Unicode 15.1.0 data files in `data/`.
+//! Every table is sorted, so lookups are binary searches.
+
+use std::cmp::Ordering;
+
+/// The [IDNA mapping status](https://www.unicode.org/reports/tr46/#Table_Data_File_Fields) of a code point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Status {
+    Valid,
+    Ignored,
+    Mapped,
+    Deviation,
+    Disallowed,
+    DisallowedStd3Valid,
+    DisallowedStd3Mapped,
+}
+
+/// The [Bidi_Class](https://www.unicode.org/reports/tr9/#Bidirectional_Character_Types) of a code point.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BidiClass {
+    L,
+    R,
+    AL,
+    EN,
+    ES,
+    ET,
+    AN,
+    CS,
+    NSM,
+    BN,
+    B,
+    S,
+    WS,
+    ON,
+    LRE,
+    LRO,
+    RLE,
+    RLO,
+    PDF,
+    LRI,
+    RLI,
+    FSI,
+    PDI,
+}
+
+/// The [Joining_Type](https://www.unicode.org/reports/tr44/#Joining_Type) of a code point, without Non_Joining.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JoiningType {
+    /// Join_Causing
+    C,
+    /// Dual_Joining
+    D,
+    /// Left_Joining
+    L,
+    /// Right_Joining
+    R,
+    /// Transparent
+    T,
+}
+
+include!(concat!(env!("OUT_DIR"), "/unicode_tables.rs"));
+
+/// Finds the range entry containing `c`.
+fn find_range<T>(table: &[T], c: u32, range: impl Fn(&T) -> (u32, u32)) -> Option<&T> {
+    table
+        .binary_search_by(|entry| {
+            let (start, end) = range(entry);
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .ok()
+        .map(|index| &table[index])
+}
+
+/// Returns the IDNA status of `c` and the code points it maps to.
+pub(crate) fn idna_mapping(c: char) -> (Status, &'static [char]) {
+    match find_range(IDNA_MAPPING, c as u32, |entry| (entry.0, entry.1)) {
+        Some(&(_, _, status, offset, length)) => {
+            let offset = offset as usize;
+            (status, &IDNA_MAPPING_POOL[offset..offset + length as usize])
+        }
+        None => (Status::Disallowed, &[]),
+    }
+}
+
+/// Returns the Bidi_Class of `c`, unlisted code points are `L`.
+pub(crate) fn bidi_class(c: char) -> BidiClass {
+    find_range(BIDI_CLASSES, c as u32, |entry| (entry.0, entry.1)).map_or(BidiClass::L, |entry| entry.2)
+}
+
+/// Returns the Joining_Type of `c`, `None` for Non_Joining code points.
+pub(crate) fn joining_type(c: char) -> Option<JoiningType> {
+    find_range(JOINING_TYPES, c as u32, |entry| (entry.0, entry.1)).map(|entry| entry.2)
+}
+
+/// Returns `true` if `c` has the General_Category Mark.
+pub(crate) fn is_mark(c: char) -> bool {
+    find_range(MARKS, c as u32, |&entry| entry).is_some()
+}
+
+/// Returns the Canonical_Combining_Class of `c`.
+pub(crate) fn combining_class(c: char) -> u8 {
+    find_range(COMBINING_CLASSES, c as u32, |entry| (entry.0, entry.1)).map_or(0, |entry| entry.2)
+}
+
+/// Returns the canonical decomposition of `c`, the second code point is missing for singletons.
+pub(crate) fn decomposition(c: char) -> Option<(char, Option<char>)> {
+    let index = DECOMPOSITIONS.binary_search_by_key(&(c as u32), |entry| entry.0).ok()?;
+    let (_, first, second) = DECOMPOSITIONS[index];
+
+    Some((char::from_u32(first)?, char::from_u32(second).filter(|&second| second != '\0')))
+}
+
+/// Returns the primary composite of `first` and `second`.
+pub(crate) fn composition(first: char, second: char) -> Option<char> {
+    let index = COMPOSITIONS.binary_search_by_key(&(first as u32, second as u32), |entry| (entry.0, entry.1)).ok()?;
+
+    char::from_u32(COMPOSITIONS[index].2)
+}
@@ -0,0 +1,587 @@
+use std::fmt::{Display, Formatter};
+use std::net::Ipv6Addr;
+use std::str::FromStr;
+
+use crate::preprocessing::{HEX_BYTE_TO_HEX_VALUE, PREPROCESSED_ARRAY};
+use crate::url::default_port;
+use crate::{normalize_escapes, UriError};
+
+/// # A URI reference validated against RFC 3986
+///
+/// Unlike [`Url`](crate::Url), nothing gets fixed up: the input has to match the
+/// [URI-reference](https://www.rfc-editor.org/rfc/rfc3986#section-4.1) grammar exactly,
+/// and the components are kept as they were written.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::Uri;
+///
+/// let uri = Uri::parse_strict("https://user@example.com:8042/over/there?name=ferret#nose").unwrap();
+/// assert_eq!(uri.scheme(), Some("https"));
+/// assert_eq!(uri.authority().as_deref(), Some("user@example.com:8042"));
+/// assert_eq!(uri.path(), "/over/there");
+/// assert_eq!(uri.query(), Some("name=ferret"));
+/// assert_eq!(uri.fragment(), Some("nose"));
+///
+/// // A browser would encode the space, a strict parser rejects it
+/// assert_eq!(Uri::parse_strict("https://example.com/a b").unwrap_err().offset(), 21);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Uri {
+    scheme: Option<String>,
+    userinfo: Option<String>,
+    host: Option<String>,
+    port: Option<String>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl Uri {
+    /// Parses a URI reference, either an absolute URI or a relative reference.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The URI reference to validate.
+    ///
+    /// # Returns
+    ///
+    /// The parsed `Uri`, or a [`UriError`] pointing at the first byte that violates the grammar.
+    pub fn parse_strict(input: &str) -> Result<Uri, UriError> {
+        let bytes = input.as_bytes();
+        let mut uri = Uri {
+            scheme: None,
+            userinfo: None,
+            host: None,
+            port: None,
+            path: String::new(),
+            query: None,
+            fragment: None,
+        };
+
+        let mut position = 0;
+        if let Some(end) = find(bytes, 0, bytes.len(), b":/?#").filter(|&end| bytes[end] == b':') {
+            validate_scheme(&bytes[..end])?;
+            uri.scheme = Some(input[..end].to_string());
+            position = end + 1;
+        }
+
+        if input[position..].starts_with("//") {
+            let start = position + 2;
+            let end = find(bytes, start, bytes.len(), b"/?#").unwrap_or(bytes.len());
+            uri.parse_authority(input, start, end)?;
+            position = end;
+        }
+
+        let end = find(bytes, position, bytes.len(), b"?#").unwrap_or(bytes.len());
+        validate(bytes, position, end, is_path_byte)?;
+        uri.path = input[position..end].to_string();
+        position = end;
+
+        if bytes.get(position) == Some(&b'?') {
+            let end = find(bytes, position + 1, bytes.len(), b"#").unwrap_or(bytes.len());
+            validate(bytes, position + 1, end, is_query_byte)?;
+            uri.query = Some(input[position + 1..end].to_string());
+            position = end;
+        }
+
+        if bytes.get(position) == Some(&b'#') {
+            validate(bytes, position + 1, bytes.len(), is_query_byte)?;
+            uri.fragment = Some(input[position + 1..].to_string());
+        }
+
+        Ok(uri)
+    }
+
+    /// Returns the scheme, `None` for a relative reference.
+    pub fn scheme(&self) -> Option<&str> {
+        self.scheme.as_deref()
+    }
+
+    /// Returns `true` if the URI has no scheme.
+    pub fn is_relative(&self) -> bool {
+        self.scheme.is_none()
+    }
+
+    /// Returns the authority, consisting of the userinfo, the host and the port.
+    pub fn authority(&self) -> Option<String> {
+        let host = self.host.as_ref()?;
+        let mut authority = String::new();
+        if let Some(userinfo) = &self.userinfo {
+            authority.push_str(userinfo);
+            authority.push('@');
+        }
+        authority.push_str(host);
+        if let Some(port) = &self.port {
+            authority.push(':');
+            authority.push_str(port);
+        }
+
+        Some(authority)
+    }
+
+    /// Returns the userinfo without the trailing `@`.
+    pub fn userinfo(&self) -> Option<&str> {
+        self.userinfo.as_deref()
+    }
+
+    /// Returns the host, IP literals include the brackets. `None` if the URI has no authority.
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Returns the port as written, it may be empty or exceed `u16` and still be valid.
+    pub fn port(&self) -> Option<&str> {
+        self.port.as_deref()
+    }
+
+    /// Returns the path, which may be empty.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the query without the leading `?`.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Returns the fragment without the leading `#`.
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment.as_deref()
+    }
+
+    fn parse_authority(&mut self, input: &str, start: usize, end: usize) -> Result<(), UriError> {
+        let bytes = input.as_bytes();
+
+        let mut host_start = start;
+        if let Some(at) = find(bytes, start, end, b"@") {
+            validate(bytes, start, at, is_userinfo_byte)?;
+            self.userinfo = Some(input[start..at].to_string());
+            host_start = at + 1;
+        }
+
+        let host_end = if bytes.get(host_start) == Some(&b'[') {
+            let close = find(bytes, host_start, end, b"]").ok_or(UriError::InvalidIpLiteral { offset: host_start })?;
+            if !is_ip_literal(&input[host_start + 1..close]) {
+                return Err(UriError::InvalidIpLiteral { offset: host_start });
+            }
+            if close + 1 < end && bytes[close + 1] != b':' {
+                return Err(UriError::InvalidCharacter { offset: close + 1, byte: bytes[close + 1] });
+            }
+            close + 1
+        } else {
+            let host_end = find(bytes, host_start, end, b":").unwrap_or(end);
+            validate(bytes, host_start, host_end, is_reg_name_byte)?;
+            host_end
+        };
+        self.host = Some(input[host_start..host_end].to_string());
+
+        if host_end < end {
+            // The byte at `host_end` is the `:`, `port = *DIGIT` allows no escapes
+            if let Some(offset) = (host_end + 1..end).find(|&index| !bytes[index].is_ascii_digit()) {
+                return Err(UriError::InvalidCharacter { offset, byte: bytes[offset] });
+            }
+            self.port = Some(input[host_end + 1..end].to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Uri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{}:", scheme)?;
+        }
+        if let Some(authority) = self.authority() {
+            write!(f, "//{}", authority)?;
+        }
+        f.write_str(&self.path)?;
+        if let Some(query) = &self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = &self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Uri {
+    type Err = UriError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Uri::parse_strict(input)
+    }
+}
+
+/// Returns the position of the first byte of `bytes[start..end]` that is one of `delimiters`.
+fn find(bytes: &[u8], start: usize, end: usize, delimiters: &[u8]) -> Option<usize> {
+    (start..end).find(|&index| delimiters.contains(&bytes[index]))
+}
+
+/// Checks that `bytes[start..end]` only contains allowed bytes and valid `pct-encoded` triplets.
+fn validate(bytes: &[u8], start: usize, end: usize, is_allowed: fn(u8) -> bool) -> Result<(), UriError> {
+    let mut index = start;
+    while index < end {
+        let byte = bytes[index];
+        if byte == b'%' {
+            if index + 2 >= end
+                || HEX_BYTE_TO_HEX_VALUE[bytes[index + 1] as usize] == -1
+                || HEX_BYTE_TO_HEX_VALUE[bytes[index + 2] as usize] == -1
+            {
+                return Err(UriError::InvalidPercentEncoding { offset: index });
+            }
+            index += 3;
+        } else if is_allowed(byte) {
+            index += 1;
+        } else {
+            return Err(UriError::InvalidCharacter { offset: index, byte });
+        }
+    }
+
+    Ok(())
+}
+
+/// `scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )`
+fn validate_scheme(scheme: &[u8]) -> Result<(), UriError> {
+    match scheme.first() {
+        Some(first) if first.is_ascii_alphabetic() => {}
+        _ => return Err(UriError::InvalidScheme { offset: 0 }),
+    }
+
+    match scheme
+        .iter()
+        .position(|&byte| !(byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'-' | b'.')))
+    {
+        Some(offset) => Err(UriError::InvalidScheme { offset }),
+        None => Ok(()),
+    }
+}
+
+/// `IP-literal = "[" ( IPv6address / IPvFuture ) "]"`, without the brackets.
+fn is_ip_literal(literal: &str) -> bool {
+    match literal.strip_prefix(['v', 'V']) {
+        // IPvFuture = "v" 1*HEXDIG "." 1*( unreserved / sub-delims / ":" )
+        Some(future) => match future.split_once('.') {
+            Some((version, address)) => {
+                !version.is_empty()
+                    && version.bytes().all(|byte| byte.is_ascii_hexdigit())
+                    && !address.is_empty()
+                    && address.bytes().all(|byte| is_unreserved(byte) || is_sub_delim(byte) || byte == b':')
+            }
+            None => false,
+        },
+        None => Ipv6Addr::from_str(literal).is_ok(),
+    }
+}
+
+fn is_unreserved(byte: u8) -> bool {
+    PREPROCESSED_ARRAY[byte as usize] == 1
+}
+
+fn is_sub_delim(byte: u8) -> bool {
+    matches!(byte, b'!' | b'$' | b'&' | b'\'' | b'(' | b')' | b'*' | b'+' | b',' | b';' | b'=')
+}
+
+fn is_reg_name_byte(byte: u8) -> bool {
+    is_unreserved(byte) || is_sub_delim(byte)
+}
+
+fn is_userinfo_byte(byte: u8) -> bool {
+    is_reg_name_byte(byte) || byte == b':'
+}
+
+/// `pchar` plus the `/` between segments.
+fn is_path_byte(byte: u8) -> bool {
+    is_userinfo_byte(byte) || byte == b'@' || byte == b'/'
+}
+
+/// The query and the fragment also allow `?`.
+fn is_query_byte(byte: u8) -> bool {
+    is_path_byte(byte) || byte == b'?'
+}
+
+/// The five components of a URI reference, split like the regular expression
+/// from [RFC 3986, Appendix B](https://www.rfc-editor.org/rfc/rfc3986#appendix-B).
+struct Components<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> Components<'a> {
+    fn split(input: &'a str) -> Self {
+        let (rest, fragment) = match input.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment)),
+            None => (input, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (scheme, rest) = match rest.find([':', '/']) {
+            Some(colon) if colon > 0 && rest.as_bytes()[colon] == b':' => (Some(&rest[..colon]), &rest[colon + 1..]),
+            _ => (None, rest),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(&rest[..end]), &rest[end..])
+            }
+            None => (None, rest),
+        };
+
+        Components {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+}
+
+/// Resolves a relative reference against a base URI, like a link on a page.
+///
+/// Implements the [transform references](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.2) algorithm
+/// in strict mode, so a reference with the same scheme as the base is not treated as relative.
+/// Both strings are split without validation, use [`Uri::parse_strict`] first if they come from untrusted input.
+///
+/// # Arguments
+///
+/// * `base` - The absolute URI the reference is relative to.
+/// * `reference` - The URI reference to resolve.
+///
+/// # Returns
+///
+/// The target URI as a new `String`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::resolve;
+///
+/// assert_eq!(resolve("http://a/b/c/d;p?q", "../g?y#s"), "http://a/b/g?y#s");
+/// assert_eq!(resolve("http://a/b/c/d;p?q", "//example.com"), "http://example.com");
+/// ```
+pub fn resolve(base: &str, reference: &str) -> String {
+    let base = Components::split(base);
+    let reference = Components::split(reference);
+
+    let scheme;
+    let authority;
+    let path;
+    let query;
+    if reference.scheme.is_some() {
+        scheme = reference.scheme;
+        authority = reference.authority;
+        path = remove_dot_segments(reference.path);
+        query = reference.query;
+    } else {
+        scheme = base.scheme;
+        if reference.authority.is_some() {
+            authority = reference.authority;
+            path = remove_dot_segments(reference.path);
+            query = reference.query;
+        } else {
+            authority = base.authority;
+            if reference.path.is_empty() {
+                path = base.path.to_string();
+                query = reference.query.or(base.query);
+            } else {
+                path = if reference.path.starts_with('/') {
+                    remove_dot_segments(reference.path)
+                } else {
+                    remove_dot_segments(&merge(&base, reference.path))
+                };
+                query = reference.query;
+            }
+        }
+    }
+
+    let mut target = String::with_capacity(base.path.len() + reference.path.len() + 16);
+    if let Some(scheme) = scheme {
+        target.push_str(scheme);
+        target.push(':');
+    }
+    if let Some(authority) = authority {
+        target.push_str("//");
+        target.push_str(authority);
+    }
+    target.push_str(&path);
+    if let Some(query) = query {
+        target.push('?');
+        target.push_str(query);
+    }
+    if let Some(fragment) = reference.fragment {
+        target.push('#');
+        target.push_str(fragment);
+    }
+
+    target
+}
+
+/// Merges a relative-path reference with the path of the base URI.
+fn merge(base: &Components<'_>, reference_path: &str) -> String {
+    if base.authority.is_some() && base.path.is_empty() {
+        return format!("/{}", reference_path);
+    }
+
+    match base.path.rfind('/') {
+        Some(slash) => format!("{}{}", &base.path[..=slash], reference_path),
+        None => reference_path.to_string(),
+    }
+}
+
+/// Removes the `.` and `..` segments from a path.
+///
+/// Implements [remove_dot_segments](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4),
+/// a `..` that would go above the root is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::remove_dot_segments;
+///
+/// assert_eq!(remove_dot_segments("/a/b/c/./../../g"), "/a/g");
+/// assert_eq!(remove_dot_segments("mid/content=5/../6"), "mid/6");
+/// assert_eq!(remove_dot_segments("/../a/."), "/a/");
+/// ```
+pub fn remove_dot_segments(path: &str) -> String {
+    let mut input = path;
+    let mut output = String::with_capacity(path.len());
+
+    while !input.is_empty() {
+        if let Some(rest) = input.strip_prefix("../").or_else(|| input.strip_prefix("./")) {
+            input = rest;
+        } else if input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input == "/.." { "/" } else { &input[3..] };
+            let last = output.rfind('/').unwrap_or(0);
+            output.truncate(last);
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            // Move the first segment, including its leading `/`, to the output
+            let end = match input.strip_prefix('/') {
+                Some(rest) => rest.find('/').map_or(input.len(), |slash| slash + 1),
+                None => input.find('/').unwrap_or(input.len()),
+            };
+            output.push_str(&input[..end]);
+            input = &input[end..];
+        }
+    }
+
+    output
+}
+
+/// Normalizes a URI so that equivalent URIs compare equal.
+///
+/// Applies the [syntax-based normalization](https://www.rfc-editor.org/rfc/rfc3986#section-6.2.2) of RFC 3986:
+///
+/// - The scheme and the host are lowercased.
+/// - Percent escapes get uppercase hex digits, escapes of unreserved characters are decoded.
+/// - Dot segments are removed from the path if the URI has a scheme or the path is absolute.
+///   The path of a relative reference like `../a` is kept, its dot segments only make
+///   sense once it is resolved against a base URI.
+///
+/// And for schemes with a known default port, like `http`, the
+/// [scheme-based normalization](https://www.rfc-editor.org/rfc/rfc3986#section-6.2.3):
+/// the default port or an empty port is dropped and an empty path becomes `/`.
+///
+/// # Arguments
+///
+/// * `uri` - The URI to normalize.
+///
+/// # Returns
+///
+/// The normalized URI as a new `String`.
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::normalize;
+///
+/// assert_eq!(normalize("HTTP://User@Example.COM:80/a/./b/../%7euser/%2f?%61=%3d"),
+///            "http://User@example.com/a/~user/%2F?a=%3D");
+/// ```
+pub fn normalize(uri: &str) -> String {
+    let components = Components::split(uri);
+    let scheme = components.scheme.map(str::to_ascii_lowercase);
+    let default_port = scheme.as_deref().and_then(default_port);
+
+    let mut normalized = String::with_capacity(uri.len());
+    if let Some(scheme) = &scheme {
+        normalized.push_str(scheme);
+        normalized.push(':');
+    }
+
+    if let Some(authority) = components.authority {
+        normalized.push_str("//");
+        let (userinfo, host_and_port) = match authority.rfind('@') {
+            Some(at) => (Some(&authority[..at]), &authority[at + 1..]),
+            None => (None, authority),
+        };
+        let port_start = match host_and_port.rfind(':') {
+            Some(colon) if !host_and_port[colon..].contains(']') => colon,
+            _ => host_and_port.len(),
+        };
+        let (host, port) = host_and_port.split_at(port_start);
+
+        if let Some(userinfo) = userinfo {
+            normalized.push_str(&normalize_escapes(userinfo, None));
+            normalized.push('@');
+        }
+        // Escapes are decoded before lowercasing, so `%41` ends up as `a`,
+        // the second pass uppercases the hex digits the lowercasing touched
+        normalized.push_str(&normalize_escapes(&normalize_escapes(host, None).to_ascii_lowercase(), None));
+        if let Some(port) = port.strip_prefix(':') {
+            let is_default = default_port.is_some_and(|default| port.is_empty() || port.parse() == Ok(default));
+            if !is_default {
+                normalized.push(':');
+                normalized.push_str(port);
+            }
+        }
+    }
+
+    let path = normalize_escapes(components.path, None);
+    let path = if scheme.is_some() || path.starts_with('/') { remove_dot_segments(&path) } else { path.into_owned() };
+    if path.is_empty() && components.authority.is_some() && default_port.is_some() {
+        normalized.push('/');
+    }
+    normalized.push_str(&path);
+
+    if let Some(query) = components.query {
+        normalized.push('?');
+        normalized.push_str(&normalize_escapes(query, None));
+    }
+    if let Some(fragment) = components.fragment {
+        normalized.push('#');
+        normalized.push_str(&normalize_escapes(fragment, None));
+    }
+
+    normalized
+}
+
+/// Returns `true` if both URIs are equal after [`normalize`].
+///
+/// # Examples
+///
+/// ```
+/// use url_encor::equivalent;
+///
+/// assert!(equivalent("http://example.com", "HTTP://EXAMPLE.COM:80/"));
+/// assert!(!equivalent("http://example.com/a", "http://example.com/A"));
+/// ```
+pub fn equivalent(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+